@@ -0,0 +1,351 @@
+// This file is part of Astar.
+
+// Copyright (C) 2019-2023 Stake Technologies Pte.Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// Astar is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Astar is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Astar. If not, see <http://www.gnu.org/licenses/>.
+
+//! Multi-asset vault on top of the pallet-assets chain extension.
+//!
+//! - deposit: moves assets from caller to the vault using `transfer_approved`,
+//!   so the caller must first approve the vault in `pallet-assets`.
+//! - withdraw: moves assets from the vault back to the caller using `transfer`.
+//!
+//! Internal balances are tracked per asset and must be either zero or at least
+//! the asset's `minimum_balance`, same as the accounts in `pallet-assets`.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use assets_extension::{AssetsError, AssetsExtension as _AssetsExtension};
+use ink::env::DefaultEnvironment;
+
+type AssetsExtension = _AssetsExtension<DefaultEnvironment>;
+
+#[ink::contract]
+mod assets_vault {
+    use super::*;
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    pub struct AssetsVault {
+        /// ID the assets chain extension is registered under in the runtime
+        extension_id: u16,
+        /// Deposited balance, `(asset_id, who) -> amount`
+        balances: Mapping<(u128, AccountId), Balance>,
+        /// Total deposited balance of each asset
+        total_deposits: Mapping<u128, Balance>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Deposited balance would be below the asset's minimum balance
+        BelowMinimumBalance,
+        /// Not enough deposited balance to withdraw
+        InsufficientDeposit,
+        /// Depositor does not have enough assets
+        DepositorBalanceLow,
+        /// Depositor's asset account would be reaped by the deposit
+        DepositorWouldDie,
+        /// Vault holds less assets than deposited, should never happen
+        VaultBalanceLow,
+        /// Vault's asset account would be reaped by the withdrawal
+        VaultWouldDie,
+        /// Deposited balance or total deposits would overflow
+        Overflow,
+        /// Any other error returned by the extension
+        Assets(AssetsError),
+    }
+
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        who: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Withdrawn {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        who: AccountId,
+        amount: Balance,
+    }
+
+    impl AssetsVault {
+        #[ink(constructor)]
+        pub fn new(extension_id: u16) -> Self {
+            Self {
+                extension_id,
+                balances: Mapping::default(),
+                total_deposits: Mapping::default(),
+            }
+        }
+
+        /// Deposit `amount` of caller's assets into the vault.
+        #[ink(message)]
+        pub fn deposit(&mut self, asset_id: u128, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let vault = self.env().account_id();
+            let balance = self
+                .balance_of(asset_id, caller)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            self.total_deposits(asset_id)
+                .checked_add(amount)
+                .ok_or(Error::Overflow)?;
+            if balance < self.assets().minimum_balance(asset_id) {
+                return Err(Error::BelowMinimumBalance);
+            }
+
+            // `transfer_approved` does not keep the depositor alive, so the
+            // vault may receive more than `amount` when the dust is swept too
+            let vault_balance = self.assets().balance_of(asset_id, vault);
+            self.assets()
+                .transfer_approved(asset_id, caller, vault, amount)
+                .map_err(|err| match err {
+                    AssetsError::BalanceLow => Error::DepositorBalanceLow,
+                    AssetsError::WouldDie => Error::DepositorWouldDie,
+                    err => Error::Assets(err),
+                })?;
+            let received = self
+                .assets()
+                .balance_of(asset_id, vault)
+                .saturating_sub(vault_balance);
+
+            let balance = self
+                .balance_of(asset_id, caller)
+                .checked_add(received)
+                .ok_or(Error::Overflow)?;
+            let total_deposits = self
+                .total_deposits(asset_id)
+                .checked_add(received)
+                .ok_or(Error::Overflow)?;
+            self.balances.insert((asset_id, caller), &balance);
+            self.total_deposits.insert(asset_id, &total_deposits);
+            self.env().emit_event(Deposited {
+                asset_id,
+                who: caller,
+                amount: received,
+            });
+            Ok(())
+        }
+
+        /// Withdraw `amount` of caller's deposited assets from the vault.
+        #[ink(message)]
+        pub fn withdraw(&mut self, asset_id: u128, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let balance = self
+                .balance_of(asset_id, caller)
+                .checked_sub(amount)
+                .ok_or(Error::InsufficientDeposit)?;
+            if balance != 0 && balance < self.assets().minimum_balance(asset_id) {
+                return Err(Error::BelowMinimumBalance);
+            }
+
+            self.assets()
+                .transfer(asset_id, caller, amount)
+                .map_err(|err| match err {
+                    AssetsError::BalanceLow => Error::VaultBalanceLow,
+                    AssetsError::WouldDie => Error::VaultWouldDie,
+                    err => Error::Assets(err),
+                })?;
+
+            if balance == 0 {
+                self.balances.remove((asset_id, caller));
+            } else {
+                self.balances.insert((asset_id, caller), &balance);
+            }
+            self.total_deposits
+                .insert(asset_id, &(self.total_deposits(asset_id) - amount));
+            self.env().emit_event(Withdrawn {
+                asset_id,
+                who: caller,
+                amount,
+            });
+            Ok(())
+        }
+
+        /// Deposited balance of `who` for the given asset.
+        #[ink(message)]
+        pub fn balance_of(&self, asset_id: u128, who: AccountId) -> Balance {
+            self.balances.get((asset_id, who)).unwrap_or_default()
+        }
+
+        /// Total deposited balance of the given asset.
+        #[ink(message)]
+        pub fn total_deposits(&self, asset_id: u128) -> Balance {
+            self.total_deposits.get(asset_id).unwrap_or_default()
+        }
+
+        fn assets(&self) -> AssetsExtension {
+            AssetsExtension::new_with_extension_id(self.extension_id)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use assets_chain_extension_types::Command;
+        use chain_extension_mock::MockExtension;
+        use core::cell::Cell;
+        use ink::env::test::{default_accounts, recorded_events, set_caller};
+        use std::rc::Rc;
+
+        const ASSET_ID: u128 = 1;
+
+        /// Assets chain extension, registered under its default ID
+        const ASSETS: MockExtension = MockExtension::new(2);
+
+        /// Registers the extension with the vault's asset balance tracked by
+        /// the transfer commands
+        fn setup(minimum_balance: Balance) -> AssetsVault {
+            let vault_balance = Rc::new(Cell::new(0));
+            ASSETS.register(Command::MinimumBalance, move |_: u128| (0, minimum_balance));
+            let balance = vault_balance.clone();
+            ASSETS.register(Command::BalanceOf, move |_: (u128, AccountId)| {
+                (0, balance.get())
+            });
+            let balance = vault_balance.clone();
+            ASSETS.register(
+                Command::TransferApproved,
+                move |(_, _, _, amount): (u128, AccountId, AccountId, Balance)| {
+                    balance.set(balance.get() + amount);
+                    (0, ())
+                },
+            );
+            ASSETS.register(
+                Command::Transfer,
+                move |(_, _, amount): (u128, AccountId, Balance)| {
+                    vault_balance.set(vault_balance.get() - amount);
+                    (0, ())
+                },
+            );
+            set_caller::<DefaultEnvironment>(default_accounts::<DefaultEnvironment>().bob);
+            AssetsVault::new(ASSETS.id())
+        }
+
+        #[ink::test]
+        fn deposit_and_withdraw_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let mut vault = setup(10);
+
+            assert_eq!(vault.deposit(ASSET_ID, 100), Ok(()));
+            assert_eq!(vault.deposit(ASSET_ID, 5), Ok(()));
+            assert_eq!(vault.balance_of(ASSET_ID, bob), 105);
+            assert_eq!(vault.total_deposits(ASSET_ID), 105);
+
+            assert_eq!(vault.withdraw(ASSET_ID, 95), Ok(()));
+            assert_eq!(vault.withdraw(ASSET_ID, 10), Ok(()));
+            assert_eq!(vault.balance_of(ASSET_ID, bob), 0);
+            assert_eq!(vault.total_deposits(ASSET_ID), 0);
+            assert_eq!(recorded_events().count(), 4);
+        }
+
+        #[ink::test]
+        fn minimum_balance_is_enforced() {
+            let mut vault = setup(10);
+
+            assert_eq!(vault.deposit(ASSET_ID, 9), Err(Error::BelowMinimumBalance));
+            assert_eq!(vault.deposit(ASSET_ID, 20), Ok(()));
+            assert_eq!(
+                vault.withdraw(ASSET_ID, 11),
+                Err(Error::BelowMinimumBalance)
+            );
+            assert_eq!(
+                vault.withdraw(ASSET_ID, 21),
+                Err(Error::InsufficientDeposit)
+            );
+        }
+
+        #[ink::test]
+        fn deposit_credits_received_amount() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let mut vault = setup(10);
+            let vault_balance = Rc::new(Cell::new(0));
+            let balance = vault_balance.clone();
+            ASSETS.register(Command::BalanceOf, move |_: (u128, AccountId)| {
+                (0, balance.get())
+            });
+            // depositor's remaining dust of 3 is swept to the vault as well
+            ASSETS.register(
+                Command::TransferApproved,
+                move |(_, _, _, amount): (u128, AccountId, AccountId, Balance)| {
+                    vault_balance.set(vault_balance.get() + amount + 3);
+                    (0, ())
+                },
+            );
+
+            assert_eq!(vault.deposit(ASSET_ID, 100), Ok(()));
+            assert_eq!(vault.balance_of(ASSET_ID, bob), 103);
+            assert_eq!(vault.total_deposits(ASSET_ID), 103);
+        }
+
+        #[ink::test]
+        fn deposit_overflow_is_rejected() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut vault = setup(1);
+            assert_eq!(vault.deposit(ASSET_ID, Balance::MAX), Ok(()));
+            ASSETS.register(
+                Command::TransferApproved,
+                |_: (u128, AccountId, AccountId, Balance)| -> (u32, ()) {
+                    panic!("assets must not be moved on overflow")
+                },
+            );
+
+            // deposited balance of the caller
+            assert_eq!(vault.deposit(ASSET_ID, 1), Err(Error::Overflow));
+            // total deposits of the asset
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(vault.deposit(ASSET_ID, 1), Err(Error::Overflow));
+            assert_eq!(vault.balance_of(ASSET_ID, accounts.alice), 0);
+            assert_eq!(vault.total_deposits(ASSET_ID), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn deposit_maps_assets_errors() {
+            let mut vault = setup(1);
+            for (err, expected) in [
+                (AssetsError::BalanceLow, Error::DepositorBalanceLow),
+                (AssetsError::WouldDie, Error::DepositorWouldDie),
+                (
+                    AssetsError::Unapproved,
+                    Error::Assets(AssetsError::Unapproved),
+                ),
+            ] {
+                ASSETS.register_status_code(Command::TransferApproved, err as u32);
+                assert_eq!(vault.deposit(ASSET_ID, 100), Err(expected));
+            }
+            assert_eq!(vault.total_deposits(ASSET_ID), 0);
+        }
+
+        #[ink::test]
+        fn withdraw_maps_assets_errors() {
+            let mut vault = setup(1);
+            assert_eq!(vault.deposit(ASSET_ID, 100), Ok(()));
+            for (err, expected) in [
+                (AssetsError::BalanceLow, Error::VaultBalanceLow),
+                (AssetsError::WouldDie, Error::VaultWouldDie),
+                (AssetsError::Frozen, Error::Assets(AssetsError::Frozen)),
+            ] {
+                ASSETS.register_status_code(Command::Transfer, err as u32);
+                assert_eq!(vault.withdraw(ASSET_ID, 100), Err(expected));
+            }
+            assert_eq!(vault.total_deposits(ASSET_ID), 100);
+        }
+    }
+}
@@ -22,15 +22,36 @@ use sp_core::H160;
 pub use unified_accounts_chain_extension_types::{Command, UnifiedAddress};
 
 /// UA Extension Interface
-pub struct UAExtension<E = DefaultEnvironment, const ID: u16 = 03>(PhantomData<E>);
+pub struct UAExtension<E = DefaultEnvironment> {
+    /// ID the extension is registered under in the runtime
+    id: u16,
+    _env: PhantomData<E>,
+}
+
+impl<E: Environment> UAExtension<E> {
+    /// ID the extension is registered under in Astar runtimes
+    pub const DEFAULT_ID: u16 = 3;
+
+    /// Extension registered under `DEFAULT_ID`
+    #[allow(dead_code)]
+    pub const fn new() -> Self {
+        Self::new_with_extension_id(Self::DEFAULT_ID)
+    }
+
+    /// Extension registered under the given `id`
+    pub const fn new_with_extension_id(id: u16) -> Self {
+        Self {
+            id,
+            _env: PhantomData,
+        }
+    }
 
-impl<E: Environment, const ID: u16> UAExtension<E, ID> {
-    const fn get_func_id(idx: u16) -> u32 {
-        ((ID as u32) << 16) + (idx as u32)
+    const fn get_func_id(&self, idx: u16) -> u32 {
+        ((self.id as u32) << 16) + (idx as u32)
     }
 
-    pub fn to_h160(account_id: E::AccountId) -> Option<H160> {
-        let func_id: u32 = Self::get_func_id(Command::GetEvmAddress.into());
+    pub fn to_h160(&self, account_id: E::AccountId) -> Option<H160> {
+        let func_id: u32 = self.get_func_id(Command::GetEvmAddress.into());
 
         // fn(AccountId) -> Option<H160>
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
@@ -40,8 +61,8 @@ impl<E: Environment, const ID: u16> UAExtension<E, ID> {
             .call(&(account_id))
     }
 
-    pub fn to_h160_or_default(account_id: E::AccountId) -> UnifiedAddress<H160> {
-        let func_id: u32 = Self::get_func_id(Command::GetEvmAddressOrDefault.into());
+    pub fn to_h160_or_default(&self, account_id: E::AccountId) -> UnifiedAddress<H160> {
+        let func_id: u32 = self.get_func_id(Command::GetEvmAddressOrDefault.into());
 
         // fn(AccountId) -> H160
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
@@ -51,8 +72,8 @@ impl<E: Environment, const ID: u16> UAExtension<E, ID> {
             .call(&(account_id))
     }
 
-    pub fn to_account_id(evm_address: H160) -> Option<E::AccountId> {
-        let func_id: u32 = Self::get_func_id(Command::GetNativeAddress.into());
+    pub fn to_account_id(&self, evm_address: H160) -> Option<E::AccountId> {
+        let func_id: u32 = self.get_func_id(Command::GetNativeAddress.into());
 
         // fn(H160) -> Option<AccountId>
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
@@ -62,8 +83,8 @@ impl<E: Environment, const ID: u16> UAExtension<E, ID> {
             .call(&(evm_address))
     }
 
-    pub fn to_account_id_or_default(evm_address: H160) -> UnifiedAddress<E::AccountId> {
-        let func_id: u32 = Self::get_func_id(Command::GetNativeAddressOrDefault.into());
+    pub fn to_account_id_or_default(&self, evm_address: H160) -> UnifiedAddress<E::AccountId> {
+        let func_id: u32 = self.get_func_id(Command::GetNativeAddressOrDefault.into());
 
         // fn(H160) -> AccountId
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
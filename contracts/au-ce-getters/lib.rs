@@ -26,41 +26,483 @@ mod helper;
 
 use helper::{UAExtension as _UAExtension, UnifiedAddress};
 use ink::env::DefaultEnvironment;
+use ink::prelude::vec::Vec;
 use sp_core::H160;
 
 type UAExtension = _UAExtension<DefaultEnvironment>;
 
+/// Runtime types for dispatching `pallet-unified-accounts` calls
+mod runtime {
+    use super::*;
+
+    #[derive(scale::Encode)]
+    pub enum RuntimeCall {
+        #[codec(index = 65)]
+        UnifiedAccounts(UnifiedAccountsCall),
+    }
+
+    #[derive(scale::Encode)]
+    pub enum UnifiedAccountsCall {
+        #[codec(index = 0)]
+        ClaimEvmAddress {
+            evm_address: H160,
+            signature: [u8; 65],
+        },
+        #[codec(index = 1)]
+        ClaimDefaultEvmAddress,
+    }
+}
+
 #[ink::contract]
 mod call_xvm_payable {
     use super::*;
 
     #[ink(storage)]
-    pub struct UAMappingGetter;
+    pub struct UAMappingGetter {
+        /// ID the unified accounts chain extension is registered under in the runtime
+        extension_id: u16,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// The claim call dispatched via `call_runtime` failed
+        ClaimFailed,
+        /// No mapping found for the contract after a successful claim
+        MappingNotFound,
+        /// Contract is mapped to a different EVM address than the claimed one
+        AddressMismatch,
+    }
+
+    /// Emitted when no mapping is found and default EVM address is returned
+    #[ink(event)]
+    pub struct DefaultEvmAddressReturned {
+        #[ink(topic)]
+        account_id: AccountId,
+        evm_address: H160,
+    }
+
+    /// Emitted when no mapping is found and default native address is returned
+    #[ink(event)]
+    pub struct DefaultAccountIdReturned {
+        #[ink(topic)]
+        evm_address: H160,
+        account_id: AccountId,
+    }
+
+    impl Default for UAMappingGetter {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
 
     impl UAMappingGetter {
+        /// Uses the UA chain extension registered under its default ID.
         #[ink(constructor)]
         pub fn new() -> Self {
-            Self {}
+            Self::new_with_extension_id(UAExtension::DEFAULT_ID)
+        }
+
+        /// Uses the UA chain extension registered under `extension_id`.
+        #[ink(constructor)]
+        pub fn new_with_extension_id(extension_id: u16) -> Self {
+            Self { extension_id }
         }
 
         #[ink(message, selector = 42)]
         pub fn to_h160(&self, account_id: AccountId) -> Option<H160> {
-            UAExtension::to_h160(account_id)
+            self.ua().to_h160(account_id)
         }
 
         #[ink(message, selector = 43)]
         pub fn to_h160_or_default(&self, account_id: AccountId) -> UnifiedAddress<H160> {
-            UAExtension::to_h160_or_default(account_id)
+            self.lookup_h160_or_default(account_id)
         }
 
         #[ink(message, selector = 44)]
         pub fn to_account_id(&self, evm_address: H160) -> Option<AccountId> {
-            UAExtension::to_account_id(evm_address)
+            self.ua().to_account_id(evm_address)
         }
 
         #[ink(message, selector = 45)]
         pub fn to_account_id_or_default(&self, evm_address: H160) -> UnifiedAddress<AccountId> {
-            UAExtension::to_account_id_or_default(evm_address)
+            self.lookup_account_id_or_default(evm_address)
+        }
+
+        /// Batch version of `to_h160_or_default`, results are in the same order as input.
+        /// Emits no events, to keep the cost of the batch to the lookups only.
+        #[ink(message, selector = 48)]
+        pub fn to_h160_or_default_batch(
+            &self,
+            account_ids: Vec<AccountId>,
+        ) -> Vec<UnifiedAddress<H160>> {
+            account_ids
+                .into_iter()
+                .map(|account_id| self.ua().to_h160_or_default(account_id))
+                .collect()
+        }
+
+        /// Batch version of `to_account_id_or_default`, results are in the same order as input.
+        /// Emits no events, to keep the cost of the batch to the lookups only.
+        #[ink(message, selector = 49)]
+        pub fn to_account_id_or_default_batch(
+            &self,
+            evm_addresses: Vec<H160>,
+        ) -> Vec<UnifiedAddress<AccountId>> {
+            evm_addresses
+                .into_iter()
+                .map(|evm_address| self.ua().to_account_id_or_default(evm_address))
+                .collect()
+        }
+
+        /// Whether the given account has an EVM address mapped.
+        #[ink(message, selector = 52)]
+        pub fn is_mapped(&self, account_id: AccountId) -> bool {
+            self.ua().to_h160(account_id).is_some()
+        }
+
+        /// Whether native -> EVM -> native lookup gives back the same account.
+        /// Only true for mapped accounts since default addresses are one-way.
+        /// Emits no events.
+        #[ink(message, selector = 53)]
+        pub fn round_trip(&self, account_id: AccountId) -> bool {
+            let evm_address = match self.ua().to_h160_or_default(account_id) {
+                UnifiedAddress::Mapped(address) | UnifiedAddress::Default(address) => address,
+            };
+            match self.ua().to_account_id_or_default(evm_address) {
+                UnifiedAddress::Mapped(native) => native == account_id,
+                UnifiedAddress::Default(_) => false,
+            }
+        }
+
+        /// Perform `to_h160_or_default` lookup `iterations` times for the same account,
+        /// used for measuring the per-lookup cost of chain extension.
+        /// Returns the result of last lookup, `None` if `iterations` is zero.
+        #[ink(message, selector = 50)]
+        pub fn to_h160_or_default_repeat(
+            &self,
+            account_id: AccountId,
+            iterations: u32,
+        ) -> Option<UnifiedAddress<H160>> {
+            (0..iterations).fold(None, |_, _| Some(self.ua().to_h160_or_default(account_id)))
+        }
+
+        /// Perform `to_account_id_or_default` lookup `iterations` times for the same address,
+        /// used for measuring the per-lookup cost of chain extension.
+        /// Returns the result of last lookup, `None` if `iterations` is zero.
+        #[ink(message, selector = 51)]
+        pub fn to_account_id_or_default_repeat(
+            &self,
+            evm_address: H160,
+            iterations: u32,
+        ) -> Option<UnifiedAddress<AccountId>> {
+            (0..iterations).fold(None, |_, _| {
+                Some(self.ua().to_account_id_or_default(evm_address))
+            })
+        }
+
+        /// Claim the default EVM address for the contract account and
+        /// return the mapped address.
+        #[ink(message, selector = 46)]
+        pub fn claim_default_evm_address(&mut self) -> Result<H160, Error> {
+            self.env()
+                .call_runtime(&runtime::RuntimeCall::UnifiedAccounts(
+                    runtime::UnifiedAccountsCall::ClaimDefaultEvmAddress,
+                ))
+                .map_err(|_| Error::ClaimFailed)?;
+
+            self.ua()
+                .to_h160(self.env().account_id())
+                .ok_or(Error::MappingNotFound)
+        }
+
+        /// Claim the given EVM address for the contract account.
+        /// The `signature` must be the EIP-712 signature of the contract account
+        /// signed by `evm_address`.
+        #[ink(message, selector = 47)]
+        pub fn claim_evm_address(
+            &mut self,
+            evm_address: H160,
+            signature: [u8; 65],
+        ) -> Result<(), Error> {
+            self.env()
+                .call_runtime(&runtime::RuntimeCall::UnifiedAccounts(
+                    runtime::UnifiedAccountsCall::ClaimEvmAddress {
+                        evm_address,
+                        signature,
+                    },
+                ))
+                .map_err(|_| Error::ClaimFailed)?;
+
+            match self.ua().to_h160(self.env().account_id()) {
+                Some(mapped) if mapped == evm_address => Ok(()),
+                Some(_) => Err(Error::AddressMismatch),
+                None => Err(Error::MappingNotFound),
+            }
+        }
+
+        #[ink(message, selector = 54)]
+        pub fn extension_id(&self) -> u16 {
+            self.extension_id
+        }
+
+        fn ua(&self) -> UAExtension {
+            UAExtension::new_with_extension_id(self.extension_id)
+        }
+
+        /// Lookup EVM address of `account_id`, emits `DefaultEvmAddressReturned`
+        /// if the account is not mapped.
+        fn lookup_h160_or_default(&self, account_id: AccountId) -> UnifiedAddress<H160> {
+            let address = self.ua().to_h160_or_default(account_id);
+            if let UnifiedAddress::Default(evm_address) = &address {
+                self.env().emit_event(DefaultEvmAddressReturned {
+                    account_id,
+                    evm_address: *evm_address,
+                });
+            }
+            address
+        }
+
+        /// Lookup native address of `evm_address`, emits `DefaultAccountIdReturned`
+        /// if the address is not mapped.
+        fn lookup_account_id_or_default(&self, evm_address: H160) -> UnifiedAddress<AccountId> {
+            let address = self.ua().to_account_id_or_default(evm_address);
+            if let UnifiedAddress::Default(account_id) = &address {
+                self.env().emit_event(DefaultAccountIdReturned {
+                    evm_address,
+                    account_id: *account_id,
+                });
+            }
+            address
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::helper::Command;
+        use chain_extension_mock::MockExtension;
+        use ink::env::test::{default_accounts, recorded_events};
+        use std::{cell::Cell, rc::Rc};
+
+        /// Unified accounts chain extension, registered under its default ID
+        const UA: MockExtension = MockExtension::new(3);
+
+        const EVM_ADDRESS: H160 = H160([0x11; 20]);
+        const DEFAULT_EVM_ADDRESS: H160 = H160([0x22; 20]);
+
+        /// Register mocks where only `alice` <-> `EVM_ADDRESS` are mapped
+        fn register_mappings() {
+            let alice = default_accounts::<DefaultEnvironment>().alice;
+            UA.register(Command::GetEvmAddress, move |account_id: AccountId| {
+                (0, (account_id == alice).then_some(EVM_ADDRESS))
+            });
+            UA.register(
+                Command::GetEvmAddressOrDefault,
+                move |account_id: AccountId| {
+                    if account_id == alice {
+                        (0, UnifiedAddress::Mapped(EVM_ADDRESS))
+                    } else {
+                        (0, UnifiedAddress::Default(DEFAULT_EVM_ADDRESS))
+                    }
+                },
+            );
+            UA.register(Command::GetNativeAddress, move |evm_address: H160| {
+                (0, (evm_address == EVM_ADDRESS).then_some(alice))
+            });
+            UA.register(
+                Command::GetNativeAddressOrDefault,
+                move |evm_address: H160| {
+                    if evm_address == EVM_ADDRESS {
+                        (0, UnifiedAddress::Mapped(alice))
+                    } else {
+                        (0, UnifiedAddress::Default(AccountId::from([0x22; 32])))
+                    }
+                },
+            );
+        }
+
+        #[ink::test]
+        fn to_h160_works() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(contract.to_h160(accounts.alice), Some(EVM_ADDRESS));
+            assert_eq!(contract.to_h160(accounts.bob), None);
+        }
+
+        #[ink::test]
+        fn to_h160_or_default_works() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(
+                contract.to_h160_or_default(accounts.alice),
+                UnifiedAddress::Mapped(EVM_ADDRESS)
+            );
+            assert_eq!(
+                contract.to_h160_or_default(accounts.bob),
+                UnifiedAddress::Default(DEFAULT_EVM_ADDRESS)
+            );
+        }
+
+        #[ink::test]
+        fn to_account_id_works() {
+            register_mappings();
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(
+                contract.to_account_id(EVM_ADDRESS),
+                Some(default_accounts::<DefaultEnvironment>().alice)
+            );
+            assert_eq!(contract.to_account_id(DEFAULT_EVM_ADDRESS), None);
+        }
+
+        #[ink::test]
+        fn to_account_id_or_default_works() {
+            register_mappings();
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(
+                contract.to_account_id_or_default(EVM_ADDRESS),
+                UnifiedAddress::Mapped(default_accounts::<DefaultEnvironment>().alice)
+            );
+            assert_eq!(
+                contract.to_account_id_or_default(DEFAULT_EVM_ADDRESS),
+                UnifiedAddress::Default(AccountId::from([0x22; 32]))
+            );
+        }
+
+        #[ink::test]
+        fn default_lookups_emit_events() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            contract.to_h160_or_default(accounts.alice);
+            contract.to_account_id_or_default(EVM_ADDRESS);
+            assert_eq!(recorded_events().count(), 0);
+
+            contract.to_h160_or_default(accounts.bob);
+            contract.to_account_id_or_default(DEFAULT_EVM_ADDRESS);
+            assert_eq!(recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn batch_lookups_emit_no_events() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            contract.to_h160_or_default_batch(vec![accounts.alice, accounts.bob]);
+            contract.to_account_id_or_default_batch(vec![EVM_ADDRESS, DEFAULT_EVM_ADDRESS]);
+            assert_eq!(recorded_events().count(), 0);
+        }
+
+        #[ink::test]
+        fn is_mapped_works() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            assert!(contract.is_mapped(accounts.alice));
+            assert!(!contract.is_mapped(accounts.bob));
+        }
+
+        #[ink::test]
+        fn round_trip_works() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            assert!(contract.round_trip(accounts.alice));
+            assert!(!contract.round_trip(accounts.bob));
+            assert_eq!(recorded_events().count(), 0);
+        }
+
+        #[ink::test]
+        fn batch_lookups_work() {
+            register_mappings();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(
+                contract.to_h160_or_default_batch(vec![accounts.bob, accounts.alice]),
+                vec![
+                    UnifiedAddress::Default(DEFAULT_EVM_ADDRESS),
+                    UnifiedAddress::Mapped(EVM_ADDRESS)
+                ]
+            );
+            assert_eq!(
+                contract.to_account_id_or_default_batch(vec![EVM_ADDRESS, DEFAULT_EVM_ADDRESS]),
+                vec![
+                    UnifiedAddress::Mapped(accounts.alice),
+                    UnifiedAddress::Default(AccountId::from([0x22; 32]))
+                ]
+            );
+            assert_eq!(contract.to_h160_or_default_batch(vec![]), vec![]);
+        }
+
+        #[ink::test]
+        fn repeat_lookups_call_extension_for_every_iteration() {
+            let calls = Rc::new(Cell::new(0));
+            let counter = calls.clone();
+            UA.register(Command::GetEvmAddressOrDefault, move |_: AccountId| {
+                counter.set(counter.get() + 1);
+                (0, UnifiedAddress::Mapped(EVM_ADDRESS))
+            });
+            let alice = default_accounts::<DefaultEnvironment>().alice;
+            let contract = UAMappingGetter::new();
+
+            assert_eq!(contract.to_h160_or_default_repeat(alice, 0), None);
+            assert_eq!(calls.get(), 0);
+            assert_eq!(
+                contract.to_h160_or_default_repeat(alice, 5),
+                Some(UnifiedAddress::Mapped(EVM_ADDRESS))
+            );
+            assert_eq!(calls.get(), 5);
+        }
+
+        #[ink::test]
+        fn claim_calls_are_encoded_for_unified_accounts_pallet() {
+            use crate::runtime::{RuntimeCall, UnifiedAccountsCall};
+            use scale::Encode;
+
+            // pallet index 65, call index 1 without arguments
+            assert_eq!(
+                RuntimeCall::UnifiedAccounts(UnifiedAccountsCall::ClaimDefaultEvmAddress).encode(),
+                vec![65, 1]
+            );
+
+            // pallet index 65, call index 0, then `evm_address` and `signature`
+            let mut expected = vec![65, 0];
+            expected.extend([0x11; 20]);
+            expected.extend([0x33; 65]);
+            assert_eq!(
+                RuntimeCall::UnifiedAccounts(UnifiedAccountsCall::ClaimEvmAddress {
+                    evm_address: EVM_ADDRESS,
+                    signature: [0x33; 65],
+                })
+                .encode(),
+                expected
+            );
+        }
+
+        #[ink::test]
+        fn constructors_set_extension_id() {
+            assert_eq!(UAMappingGetter::new().extension_id(), UA.id());
+            assert_eq!(UAMappingGetter::new_with_extension_id(7).extension_id(), 7);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "missing chain extension method")]
+        fn lookups_with_unregistered_extension_id_fail() {
+            register_mappings();
+            let alice = default_accounts::<DefaultEnvironment>().alice;
+
+            UAMappingGetter::new_with_extension_id(UA.id() + 1).to_h160(alice);
         }
     }
 }
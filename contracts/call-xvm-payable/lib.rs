@@ -19,6 +19,8 @@
 //! Contract for cross-VM payable calls test.
 //! 
 //! This contract can call an EVM contract via `pallet-xvm` via Chain Extension.
+//! The transferred value can be forwarded as a whole, partially (contract keeps the
+//! remainder) or refunded to the caller when the EVM execution fails.
 
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
@@ -48,10 +50,251 @@ mod call_xvm_payable {
             // Calling EVM
             Self::env().extension().xvm_call(0x0F, target, input, value)
         }
+
+        /// Forward only `forward_value` of the transferred value to the EVM target,
+        /// the remainder is kept by the contract.
+        #[ink(message, payable, selector = 43)]
+        pub fn call_xvm_payable_partial(
+            &self,
+            target: Vec<u8>,
+            input: Vec<u8>,
+            forward_value: Balance,
+        ) -> Result<Vec<u8>, Error> {
+            if forward_value > Self::env().transferred_value() {
+                return Err(Error::ForwardValueTooHigh);
+            }
+            // Calling EVM
+            Self::env()
+                .extension()
+                .xvm_call(0x0F, target, input, forward_value)
+                .map_err(Error::XvmCall)
+        }
+
+        /// Forward the whole transferred value to the EVM target and refund it to
+        /// the caller if the EVM execution fails.
+        ///
+        /// `ExecutionFailed` is returned inside `Ok` so that the refund is not
+        /// rolled back, any other XVM error reverts the whole call.
+        #[ink(message, payable, selector = 44)]
+        pub fn call_xvm_payable_or_refund(
+            &self,
+            target: Vec<u8>,
+            input: Vec<u8>,
+        ) -> Result<Result<Vec<u8>, XvmCallError>, Error> {
+            let value = Self::env().transferred_value();
+            // Calling EVM
+            match Self::env().extension().xvm_call(0x0F, target, input, value) {
+                Err(XvmCallError::ExecutionFailed) => {
+                    Self::env()
+                        .transfer(Self::env().caller(), value)
+                        .map_err(|_| Error::RefundFailed)?;
+                    Ok(Err(XvmCallError::ExecutionFailed))
+                }
+                Err(err) => Err(Error::XvmCall(err)),
+                Ok(output) => Ok(Ok(output)),
+            }
+        }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test;
+        use scale::{Decode, Encode};
+        use std::{cell::Cell, rc::Rc};
+
+        /// Mock XVM extension which returns the given status code and output for
+        /// every call and records the value forwarded to EVM.
+        struct MockXvm {
+            status_code: u32,
+            output: Vec<u8>,
+            forwarded: Rc<Cell<Option<Balance>>>,
+        }
+
+        impl test::ChainExtension for MockXvm {
+            fn func_id(&self) -> u32 {
+                0x00010001
+            }
+
+            fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+                // off-chain engine encodes the already encoded input once more
+                let input = Vec::<u8>::decode(&mut &input[..]).unwrap();
+                let (vm_id, _target, _input, value) =
+                    <(u8, Vec<u8>, Vec<u8>, Balance)>::decode(&mut &input[..]).unwrap();
+                assert_eq!(vm_id, 0x0F);
+                self.forwarded.set(Some(value));
+                self.output.encode_to(output);
+                self.status_code
+            }
+        }
+
+        /// Register the mock XVM extension and return handle to forwarded value
+        fn register_xvm(status_code: u32, output: Vec<u8>) -> Rc<Cell<Option<Balance>>> {
+            let forwarded = Rc::new(Cell::new(None));
+            test::register_chain_extension(MockXvm {
+                status_code,
+                output,
+                forwarded: forwarded.clone(),
+            });
+            forwarded
+        }
+
+        fn all_errors() -> Vec<(u32, XvmCallError)> {
+            vec![
+                (1, XvmCallError::InvalidVmId),
+                (2, XvmCallError::SameVmCallNotAllowed),
+                (3, XvmCallError::InvalidTarget),
+                (4, XvmCallError::InputTooLarge),
+                (5, XvmCallError::BadOrigin),
+                (6, XvmCallError::ExecutionFailed),
+            ]
+        }
+
+        fn setup(transferred: Balance) -> CallXvmPayable {
+            let accounts = test::default_accounts::<CustomEnvironment>();
+            let contract = CallXvmPayable::new();
+            test::set_caller::<CustomEnvironment>(accounts.bob);
+            test::set_account_balance::<CustomEnvironment>(accounts.bob, 0);
+            test::set_account_balance::<CustomEnvironment>(
+                test::callee::<CustomEnvironment>(),
+                transferred,
+            );
+            test::set_value_transferred::<CustomEnvironment>(transferred);
+            contract
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_works() {
+            let contract = setup(100);
+            let forwarded = register_xvm(0, vec![1, 2, 3]);
+
+            assert_eq!(
+                contract.call_xvm_payable(vec![0xAA], vec![0xBB]),
+                Ok(vec![1, 2, 3])
+            );
+            assert_eq!(forwarded.get(), Some(100));
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_returns_xvm_errors() {
+            let contract = setup(100);
+            for (status_code, err) in all_errors() {
+                register_xvm(status_code, vec![]);
+                assert_eq!(contract.call_xvm_payable(vec![0xAA], vec![0xBB]), Err(err));
+            }
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_returns_unknown_status_code() {
+            let contract = setup(100);
+            register_xvm(7, vec![]);
+
+            assert_eq!(
+                contract.call_xvm_payable(vec![0xAA], vec![0xBB]),
+                Err(XvmCallError::UnknownStatusCode(7))
+            );
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_partial_works() {
+            let contract = setup(100);
+            let forwarded = register_xvm(0, vec![1]);
+
+            assert_eq!(
+                contract.call_xvm_payable_partial(vec![0xAA], vec![0xBB], 60),
+                Ok(vec![1])
+            );
+            assert_eq!(forwarded.get(), Some(60));
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_partial_fails_if_forward_value_too_high() {
+            let contract = setup(100);
+            let forwarded = register_xvm(0, vec![1]);
+
+            assert_eq!(
+                contract.call_xvm_payable_partial(vec![0xAA], vec![0xBB], 101),
+                Err(Error::ForwardValueTooHigh)
+            );
+            assert_eq!(forwarded.get(), None);
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_partial_returns_xvm_errors() {
+            let contract = setup(100);
+            for (status_code, err) in all_errors() {
+                register_xvm(status_code, vec![]);
+                assert_eq!(
+                    contract.call_xvm_payable_partial(vec![0xAA], vec![0xBB], 60),
+                    Err(Error::XvmCall(err))
+                );
+            }
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_or_refund_works() {
+            let contract = setup(100);
+            let forwarded = register_xvm(0, vec![1]);
+
+            assert_eq!(
+                contract.call_xvm_payable_or_refund(vec![0xAA], vec![0xBB]),
+                Ok(Ok(vec![1]))
+            );
+            assert_eq!(forwarded.get(), Some(100));
+            assert_eq!(
+                test::get_account_balance::<CustomEnvironment>(
+                    test::default_accounts::<CustomEnvironment>().bob
+                ),
+                Ok(0)
+            );
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_or_refund_refunds_on_execution_failure() {
+            let contract = setup(100);
+            register_xvm(6, vec![]);
+
+            assert_eq!(
+                contract.call_xvm_payable_or_refund(vec![0xAA], vec![0xBB]),
+                Ok(Err(XvmCallError::ExecutionFailed))
+            );
+            assert_eq!(
+                test::get_account_balance::<CustomEnvironment>(
+                    test::default_accounts::<CustomEnvironment>().bob
+                ),
+                Ok(100)
+            );
+        }
+
+        #[ink::test]
+        fn call_xvm_payable_or_refund_reverts_on_other_errors() {
+            let contract = setup(100);
+            for (status_code, err) in all_errors()
+                .into_iter()
+                .filter(|(_, err)| *err != XvmCallError::ExecutionFailed)
+            {
+                register_xvm(status_code, vec![]);
+                assert_eq!(
+                    contract.call_xvm_payable_or_refund(vec![0xAA], vec![0xBB]),
+                    Err(Error::XvmCall(err))
+                );
+            }
+        }
+    }
+}
+
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum Error {
+    /// Value to forward is greater than the transferred value
+    ForwardValueTooHigh,
+    /// XVM call returned an error
+    XvmCall(XvmCallError),
+    /// Refunding the transferred value to the caller failed
+    RefundFailed,
 }
 
-#[derive(scale::Encode, scale::Decode, Debug)]
+#[derive(scale::Encode, scale::Decode, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
 pub enum XvmCallError {
     InvalidVmId,
@@ -60,6 +303,7 @@ pub enum XvmCallError {
     InputTooLarge,
     BadOrigin,
     ExecutionFailed,
+    UnknownStatusCode(u32),
 }
 impl FromStatusCode for XvmCallError {
     fn from_status_code(status_code: u32) -> Result<(), Self> {
@@ -71,7 +315,7 @@ impl FromStatusCode for XvmCallError {
             4 => Err(Self::InputTooLarge),
             5 => Err(Self::BadOrigin),
             6 => Err(Self::ExecutionFailed),
-            _ => panic!("unknown status code"),
+            _ => Err(Self::UnknownStatusCode(status_code)),
         }
     }
 }
@@ -85,7 +329,7 @@ impl From<scale::Error> for XvmCallError {
 pub trait XvmCall {
     type ErrorCode = XvmCallError;
 
-    #[ink(extension = 0x00010001, handle_status = false)]
+    #[ink(extension = 0x00010001)]
     fn xvm_call(
         vm_id: u8,
         target: Vec<u8>,
@@ -0,0 +1,74 @@
+//! Contract failure modes on instantiation, each behind its own selector:
+//! - 42 `new`: succeeds, the transferred value is returned by `endowment` (47)
+//! - 43 `new_revert`: returns `Err(Error::DummyError)`
+//! - 44 `new_panic`: panics
+//! - 45 `new_reject_value`: returns `Err(Error::RejectedValue)` only if the
+//!   transferred value equals `rejected`
+//! - 46 `new_write_and_revert`: writes `items` storage entries before returning
+//!   `Err(Error::DummyError)`, for checking the storage deposit refund
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod constructor_error {
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct ConstructorError {
+        items: Mapping<u32, [u8; 32]>,
+        /// Value transferred on instantiation
+        endowment: Balance,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        #[codec(index = 7)]
+        DummyError,
+        #[codec(index = 8)]
+        RejectedValue,
+    }
+
+    impl ConstructorError {
+        #[ink(constructor, payable, selector = 42)]
+        pub fn new() -> Self {
+            Self {
+                endowment: Self::env().transferred_value(),
+                ..Default::default()
+            }
+        }
+
+        #[ink(constructor, selector = 43)]
+        pub fn new_revert() -> Result<Self, Error> {
+            Err(Error::DummyError)
+        }
+
+        #[ink(constructor, selector = 44)]
+        pub fn new_panic() -> Self {
+            panic!("dummy constructor panic")
+        }
+
+        #[ink(constructor, payable, selector = 45)]
+        pub fn new_reject_value(rejected: Balance) -> Result<Self, Error> {
+            if Self::env().transferred_value() == rejected {
+                return Err(Error::RejectedValue);
+            }
+            Ok(Self::new())
+        }
+
+        #[ink(constructor, selector = 46)]
+        pub fn new_write_and_revert(items: u32) -> Result<Self, Error> {
+            let mut contract = Self::new();
+            for key in 0..items {
+                contract.items.insert(key, &[0xff; 32]);
+            }
+            Err(Error::DummyError)
+        }
+
+        #[ink(message, selector = 47)]
+        pub fn endowment(&self) -> Balance {
+            self.endowment
+        }
+    }
+}
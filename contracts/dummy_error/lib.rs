@@ -1,27 +1,253 @@
+//! Contract failure modes, each behind its own selector:
+//! - 42 `do_revert`: returns `Err(Error::DummyError)`
+//! - 43 `do_panic`: panics
+//! - 44 `do_unreachable`: executes the wasm `unreachable` instruction
+//! - 45 `do_overflow`: traps on integer overflow, needs `overflow-checks`
+//! - 46 `do_out_of_gas`: loops until it runs out of gas
+//! - 47 `fill_storage`: writes `items` storage entries, exceeds the storage
+//!   deposit limit if called with a lower limit than required
+//! - 48 `call_unknown_selector`: calls `UNKNOWN_SELECTOR` of `callee` and returns
+//!   the `LangError` it fails with. Calling `UNKNOWN_SELECTOR` of this contract
+//!   directly gives the same error.
+//! - 49 `revert_with_payload`: reverts with `size` bytes, bypassing the message
+//!   return type
+//!
+//! Reverts with structured `Error` data of configurable size:
+//! - 50 `revert_with_message`: `Error::Message` with a string of `len` characters
+//! - 51 `revert_with_bytes`: `Error::Payload` with `len` bytes
+//! - 52 `revert_with_nested`: `Error::Nested` with `depth` levels of `NestedError`
+//!
+//! Side effects followed by `Err(Error::DummyError)`, getters prove the rollback:
+//! - 53 `write_and_revert`: writes `value` to storage, see `value`
+//! - 54 `emit_and_revert`: emits `Emitted` and counts it, see `events_emitted`
+//! - 55 `transfer_and_revert`: transfers `amount` to `to`, see `balance`
+//! - 56 `call_extension_and_revert`: calls chain extension `func_id` with raw `input`,
+//!   see `call_extension` for querying the extension state
+
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
 #[ink::contract]
 mod dummy_error {
+    use ink::env::call::{build_call, ExecutionInput, Selector};
+    use ink::env::chain_extension::ChainExtensionMethod;
+    use ink::env::ReturnFlags;
+    use ink::prelude::{boxed::Box, string::String, vec, vec::Vec};
+    use ink::storage::{Lazy, Mapping};
+    use ink::LangError;
+
+    /// Selector which no message of this contract uses
+    pub const UNKNOWN_SELECTOR: [u8; 4] = [0xde, 0xad, 0xbe, 0xef];
 
     #[ink(storage)]
-    pub struct DummyError {}
-    
+    #[derive(Default)]
+    pub struct DummyError {
+        items: Mapping<u32, [u8; 32]>,
+        items_count: u32,
+        /// `Lazy` fields are written to storage immediately and not only
+        /// on successful message return
+        value: Lazy<u32>,
+        events_emitted: Lazy<u32>,
+    }
+
+    #[ink(event)]
+    pub struct Emitted {
+        count: u32,
+    }
+
+    /// Chain extension input passed as is, without length prefix
+    struct RawInput(Vec<u8>);
+
+    impl scale::Encode for RawInput {
+        fn size_hint(&self) -> usize {
+            self.0.len()
+        }
+
+        fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+            dest.write(&self.0)
+        }
+    }
+
+    /// Chain extension output taken as is, without length prefix
+    struct RawOutput(Vec<u8>);
+
+    impl scale::Decode for RawOutput {
+        fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+            let mut output = vec![0; input.remaining_len()?.unwrap_or_default()];
+            input.read(&mut output)?;
+            Ok(Self(output))
+        }
+    }
+
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    #[allow(clippy::enum_variant_names)]
     pub enum Error {
         #[codec(index = 7)]
         DummyError,
+        #[codec(index = 8)]
+        Message(String),
+        #[codec(index = 9)]
+        Payload(Vec<u8>),
+        #[codec(index = 10)]
+        Nested(NestedError),
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum NestedError {
+        Leaf { code: u32, reason: String },
+        Inner(Box<NestedError>),
     }
 
     impl DummyError {
         #[ink(constructor)]
         pub fn new() -> Self {
-            Self {}
+            Self::default()
         }
-        
+
         #[ink(message, selector = 42)]
         pub fn do_revert(&self) -> Result<(), Error> {
             Err(Error::DummyError)
         }
+
+        #[ink(message, selector = 43)]
+        pub fn do_panic(&self) {
+            panic!("dummy panic")
+        }
+
+        /// Traps with the wasm `unreachable` instruction, off-chain it panics
+        /// instead.
+        #[ink(message, selector = 44)]
+        pub fn do_unreachable(&self) {
+            #[cfg(target_arch = "wasm32")]
+            core::arch::wasm32::unreachable();
+            #[cfg(not(target_arch = "wasm32"))]
+            unreachable!()
+        }
+
+        /// Returns `u32::MAX + value`, traps for any non-zero `value`. Relies on
+        /// `overflow-checks = true` in `[profile.release]` of `Cargo.toml`, the
+        /// addition silently wraps without it.
+        #[ink(message, selector = 45)]
+        pub fn do_overflow(&self, value: u32) -> u32 {
+            core::hint::black_box(u32::MAX) + value
+        }
+
+        #[ink(message, selector = 46)]
+        pub fn do_out_of_gas(&self) {
+            let mut counter: u64 = 0;
+            loop {
+                counter = core::hint::black_box(counter.wrapping_add(1));
+            }
+        }
+
+        /// Write `items` new storage entries of 32 bytes each.
+        #[ink(message, selector = 47)]
+        pub fn fill_storage(&mut self, items: u32) {
+            for _ in 0..items {
+                self.items.insert(self.items_count, &[0xff; 32]);
+                self.items_count += 1;
+            }
+        }
+
+        #[ink(message, selector = 48)]
+        pub fn call_unknown_selector(&self, callee: AccountId) -> Result<(), LangError> {
+            build_call::<Environment>()
+                .call(callee)
+                .exec_input(ExecutionInput::new(Selector::new(UNKNOWN_SELECTOR)))
+                .returns::<()>()
+                .try_invoke()
+                .unwrap_or_else(|err| panic!("call failed: {err:?}"))
+        }
+
+        /// Revert with `size` bytes of `0xff` encoded as `Vec<u8>`.
+        #[ink(message, selector = 49)]
+        pub fn revert_with_payload(&self, size: u32) {
+            ink::env::return_value::<Vec<u8>>(
+                ReturnFlags::new_with_reverted(true),
+                &vec![0xff; size as usize],
+            )
+        }
+
+        #[ink(message, selector = 50)]
+        pub fn revert_with_message(&self, len: u32) -> Result<(), Error> {
+            Err(Error::Message("x".repeat(len as usize)))
+        }
+
+        #[ink(message, selector = 51)]
+        pub fn revert_with_bytes(&self, len: u32) -> Result<(), Error> {
+            Err(Error::Payload(vec![0xff; len as usize]))
+        }
+
+        /// Revert with `depth` levels of `NestedError::Inner` around a `NestedError::Leaf`.
+        #[ink(message, selector = 52)]
+        pub fn revert_with_nested(&self, depth: u32) -> Result<(), Error> {
+            let leaf = NestedError::Leaf {
+                code: depth,
+                reason: String::from("dummy nested error"),
+            };
+            let nested = (0..depth).fold(leaf, |inner, _| NestedError::Inner(Box::new(inner)));
+            Err(Error::Nested(nested))
+        }
+
+        #[ink(message, selector = 53)]
+        pub fn write_and_revert(&mut self, value: u32) -> Result<(), Error> {
+            self.value.set(&value);
+            Err(Error::DummyError)
+        }
+
+        #[ink(message, selector = 54)]
+        pub fn emit_and_revert(&mut self) -> Result<(), Error> {
+            let count = self.events_emitted().saturating_add(1);
+            self.events_emitted.set(&count);
+            self.env().emit_event(Emitted { count });
+            Err(Error::DummyError)
+        }
+
+        /// Transfer `amount` from contract's balance, including the transferred value.
+        #[ink(message, payable, selector = 55)]
+        pub fn transfer_and_revert(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.env()
+                .transfer(to, amount)
+                .unwrap_or_else(|err| panic!("transfer failed: {err:?}"));
+            Err(Error::DummyError)
+        }
+
+        /// Call the extension, ignoring its status code & output.
+        #[ink(message, selector = 56)]
+        pub fn call_extension_and_revert(
+            &mut self,
+            func_id: u32,
+            input: Vec<u8>,
+        ) -> Result<(), Error> {
+            self.call_extension(func_id, input);
+            Err(Error::DummyError)
+        }
+
+        #[ink(message, selector = 57)]
+        pub fn value(&self) -> u32 {
+            self.value.get().unwrap_or_default()
+        }
+
+        #[ink(message, selector = 58)]
+        pub fn events_emitted(&self) -> u32 {
+            self.events_emitted.get().unwrap_or_default()
+        }
+
+        #[ink(message, selector = 59)]
+        pub fn balance(&self) -> Balance {
+            self.env().balance()
+        }
+
+        /// Call chain extension `func_id` with raw `input`, returns the raw output.
+        #[ink(message, selector = 60)]
+        pub fn call_extension(&self, func_id: u32, input: Vec<u8>) -> Vec<u8> {
+            let RawOutput(output) = ChainExtensionMethod::build(func_id)
+                .input::<RawInput>()
+                .output::<RawOutput, false>()
+                .ignore_error_code()
+                .call(&RawInput(input));
+            output
+        }
     }
 }
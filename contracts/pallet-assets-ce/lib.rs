@@ -16,28 +16,279 @@
 // You should have received a copy of the GNU General Public License
 // along with Astar. If not, see <http://www.gnu.org/licenses/>.
 
+//! Fixture for the `pallet-assets` chain extension.
+//!
+//! Foreign (XC20) assets are used like any other asset, by their `u128` asset
+//! ID. Their `MultiLocation` to asset ID mapping in `pallet-xc-asset-config`
+//! is not available to contracts: the runtime has no chain extension for it
+//! and `call_runtime` can't return data, so the contract doesn't resolve
+//! locations.
+
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-mod helper;
+#[cfg(test)]
+mod ledger;
 
-use helper::AssetsExtension as _AssetsExtension;
+use assets_extension::AssetsExtension as _AssetsExtension;
 use ink::env::DefaultEnvironment;
-use crate::helper::AssetsError;
+use assets_extension::AssetsError;
 use ink::prelude::vec::Vec;
 
 type AssetsExtension = _AssetsExtension<DefaultEnvironment>;
 
+/// Runtime types for dispatching `pallet-assets` calls which have no chain
+/// extension command
+mod runtime {
+    use ink::prelude::vec::Vec;
+    use ink::primitives::AccountId;
+
+    #[derive(scale::Encode)]
+    pub enum RuntimeCall {
+        #[codec(index = 36)]
+        Assets(AssetsCall),
+    }
+
+    /// `AccountIdLookupOf` of the runtime, only account IDs are used
+    #[derive(scale::Encode)]
+    pub enum MultiAddress {
+        #[codec(index = 0)]
+        Id(AccountId),
+    }
+
+    impl From<AccountId> for MultiAddress {
+        fn from(account_id: AccountId) -> Self {
+            MultiAddress::Id(account_id)
+        }
+    }
+
+    /// Asset IDs are `Compact<u128>` as the runtime's `AssetIdParameter`
+    #[derive(scale::Encode)]
+    pub enum AssetsCall {
+        #[codec(index = 0)]
+        Create {
+            #[codec(compact)]
+            id: u128,
+            admin: MultiAddress,
+            min_balance: u128,
+        },
+        #[codec(index = 2)]
+        StartDestroy {
+            #[codec(compact)]
+            id: u128,
+        },
+        #[codec(index = 3)]
+        DestroyAccounts {
+            #[codec(compact)]
+            id: u128,
+        },
+        #[codec(index = 4)]
+        DestroyApprovals {
+            #[codec(compact)]
+            id: u128,
+        },
+        #[codec(index = 5)]
+        FinishDestroy {
+            #[codec(compact)]
+            id: u128,
+        },
+        #[codec(index = 11)]
+        Freeze {
+            #[codec(compact)]
+            id: u128,
+            who: MultiAddress,
+        },
+        #[codec(index = 12)]
+        Thaw {
+            #[codec(compact)]
+            id: u128,
+            who: MultiAddress,
+        },
+        #[codec(index = 17)]
+        SetMetadata {
+            #[codec(compact)]
+            id: u128,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+            decimals: u8,
+        },
+        #[codec(index = 23)]
+        CancelApproval {
+            #[codec(compact)]
+            id: u128,
+            delegate: MultiAddress,
+        },
+    }
+}
+
 #[ink::contract]
 pub mod contract {
     use super::*;
+    use crate::runtime::{AssetsCall, RuntimeCall};
 
     #[ink(storage)]
-    pub struct Mock;
+    pub struct Mock {
+        /// ID the assets chain extension is registered under in the runtime
+        extension_id: u16,
+    }
+
+    /// Account whose assets the `_as` messages act on.
+    ///
+    /// The assets extension has no origin mode, every extension call is
+    /// dispatched with the contract as origin. Acting on the caller's assets is
+    /// only possible by moving them with the approval the caller has given to
+    /// the contract.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Origin {
+        /// Contract's own assets
+        Contract,
+        /// Caller's assets, moved with `transfer_approved` from the caller
+        Caller,
+    }
+
+    /// Errors raised by the contract itself, extension errors are returned as
+    /// `Assets` so they can't be mistaken for each other
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// `Origin::Caller` used for a call the extension can only dispatch
+        /// with the contract as origin
+        CallerOriginUnsupported,
+        /// The `pallet-assets` call dispatched via `call_runtime` failed
+        CallRuntimeFailed,
+        /// Error returned by the extension
+        Assets(AssetsError),
+    }
+
+    impl From<AssetsError> for Error {
+        fn from(error: AssetsError) -> Self {
+            Error::Assets(error)
+        }
+    }
+
+    /// Asset operation executed by `batch` and `trigger_error`, same as the
+    /// message of the same name
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Operation {
+        Mint {
+            asset_id: u128,
+            beneficiary: AccountId,
+            amount: Balance,
+        },
+        Burn {
+            asset_id: u128,
+            who: AccountId,
+            amount: Balance,
+        },
+        Transfer {
+            asset_id: u128,
+            target: AccountId,
+            amount: Balance,
+        },
+        ApproveTransfer {
+            asset_id: u128,
+            delegate: AccountId,
+            amount: Balance,
+        },
+        TransferApproved {
+            asset_id: u128,
+            owner: AccountId,
+            destination: AccountId,
+            amount: Balance,
+        },
+    }
+
+    /// How `batch` handles a failing operation
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum BatchMode {
+        /// Stop at the first error and return it, reverting the whole batch
+        StopOnError,
+        /// Execute all the operations and return the result of each one,
+        /// successful operations are kept even if others fail
+        CollectResults,
+    }
+
+    /// Error returned by `batch` in `BatchMode::StopOnError`
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct BatchError {
+        /// Index of the failed operation
+        pub index: u32,
+        /// Error returned by the failed operation
+        pub error: AssetsError,
+    }
+
+    #[ink(event)]
+    pub struct Minted {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        beneficiary: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Burned {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        who: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Transferred {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approved {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        delegate: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferredApproved {
+        #[ink(topic)]
+        asset_id: u128,
+        #[ink(topic)]
+        owner: AccountId,
+        delegate: AccountId,
+        #[ink(topic)]
+        destination: AccountId,
+        amount: Balance,
+    }
+
+    impl Default for Mock {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
 
     impl Mock {
+        /// Uses the assets chain extension registered under its default ID.
         #[ink(constructor)]
         pub fn new() -> Self {
-            Self {}
+            Self::new_with_extension_id(AssetsExtension::DEFAULT_ID)
+        }
+
+        /// Uses the assets chain extension registered under `extension_id`.
+        #[ink(constructor)]
+        pub fn new_with_extension_id(extension_id: u16) -> Self {
+            Self { extension_id }
         }
 
         #[ink(message)]
@@ -47,7 +298,12 @@ pub mod contract {
             beneficiary: AccountId,
             amount: Balance,
         ) -> Result<(), AssetsError> {
-            AssetsExtension::mint(asset_id, beneficiary, amount)?;
+            self.assets().mint(asset_id, beneficiary, amount)?;
+            self.env().emit_event(Minted {
+                asset_id,
+                beneficiary,
+                amount,
+            });
             Ok(())
         }
 
@@ -58,7 +314,12 @@ pub mod contract {
             who: AccountId,
             amount: Balance,
         ) -> Result<(), AssetsError> {
-            AssetsExtension::burn(asset_id, who, amount)?;
+            self.assets().burn(asset_id, who, amount)?;
+            self.env().emit_event(Burned {
+                asset_id,
+                who,
+                amount,
+            });
             Ok(())
         }
 
@@ -69,7 +330,13 @@ pub mod contract {
             target: AccountId,
             amount: Balance,
         ) -> Result<(), AssetsError> {
-            AssetsExtension::transfer(asset_id, target, amount)?;
+            self.assets().transfer(asset_id, target, amount)?;
+            self.env().emit_event(Transferred {
+                asset_id,
+                from: self.env().account_id(),
+                to: target,
+                amount,
+            });
             Ok(())
         }
 
@@ -80,7 +347,13 @@ pub mod contract {
             delegate: AccountId,
             amount: Balance,
         ) -> Result<(), AssetsError> {
-            AssetsExtension::approve_transfer(asset_id, delegate, amount)?;
+            self.assets().approve_transfer(asset_id, delegate, amount)?;
+            self.env().emit_event(Approved {
+                asset_id,
+                owner: self.env().account_id(),
+                delegate,
+                amount,
+            });
             Ok(())
         }
 
@@ -92,48 +365,1136 @@ pub mod contract {
             destination: AccountId,
             amount: Balance,
         ) -> Result<(), AssetsError> {
-            AssetsExtension::transfer_approved(
+            self.assets()
+                .transfer_approved(asset_id, owner, destination, amount)?;
+            self.env().emit_event(TransferredApproved {
                 asset_id,
                 owner,
+                delegate: self.env().account_id(),
                 destination,
                 amount,
-            )?;
+            });
+            Ok(())
+        }
+
+        /// Transfer `amount` to `target` from the contract's (`Origin::Contract`)
+        /// or the caller's (`Origin::Caller`) assets. Caller's assets are moved
+        /// with `transfer_approved`, so the caller must approve the contract first.
+        #[ink(message)]
+        pub fn transfer_as(
+            &mut self,
+            origin: Origin,
+            asset_id: u128,
+            target: AccountId,
+            amount: Balance,
+        ) -> Result<(), Error> {
+            match origin {
+                Origin::Contract => self.transfer(asset_id, target, amount)?,
+                Origin::Caller => {
+                    self.transfer_approved(asset_id, self.env().caller(), target, amount)?
+                }
+            }
             Ok(())
         }
 
+        /// Transfer `amount` of `owner` assets to `destination` using the approval
+        /// `owner` has given to the contract. Only `Origin::Contract` is supported,
+        /// the extension can't use an approval given to the caller.
+        #[ink(message)]
+        pub fn transfer_approved_as(
+            &mut self,
+            origin: Origin,
+            asset_id: u128,
+            owner: AccountId,
+            destination: AccountId,
+            amount: Balance,
+        ) -> Result<(), Error> {
+            match origin {
+                Origin::Contract => {
+                    Ok(self.transfer_approved(asset_id, owner, destination, amount)?)
+                }
+                Origin::Caller => Err(Error::CallerOriginUnsupported),
+            }
+        }
+
+        /// Burn `amount` of the contract's assets. Only `Origin::Contract` is
+        /// supported, burning is done by the asset's admin and not by the owner
+        /// of the assets.
+        #[ink(message)]
+        pub fn burn_as(
+            &mut self,
+            origin: Origin,
+            asset_id: u128,
+            amount: Balance,
+        ) -> Result<(), Error> {
+            match origin {
+                Origin::Contract => Ok(self.burn(asset_id, self.env().account_id(), amount)?),
+                Origin::Caller => Err(Error::CallerOriginUnsupported),
+            }
+        }
+
+        /// Create asset `asset_id` with the contract as owner and `admin` as admin,
+        /// issuer and freezer. Dispatched via `call_runtime`, same as the rest of the
+        /// lifecycle calls, so the runtime's error is not available to the contract.
+        #[ink(message)]
+        pub fn create(
+            &mut self,
+            asset_id: u128,
+            admin: AccountId,
+            min_balance: Balance,
+        ) -> Result<(), Error> {
+            self.call_assets(AssetsCall::Create {
+                id: asset_id,
+                admin: admin.into(),
+                min_balance,
+            })
+        }
+
+        #[ink(message)]
+        pub fn set_metadata(
+            &mut self,
+            asset_id: u128,
+            name: Vec<u8>,
+            symbol: Vec<u8>,
+            decimals: u8,
+        ) -> Result<(), Error> {
+            self.call_assets(AssetsCall::SetMetadata {
+                id: asset_id,
+                name,
+                symbol,
+                decimals,
+            })
+        }
+
+        #[ink(message)]
+        pub fn cancel_approval(
+            &mut self,
+            asset_id: u128,
+            delegate: AccountId,
+        ) -> Result<(), Error> {
+            self.call_assets(AssetsCall::CancelApproval {
+                id: asset_id,
+                delegate: delegate.into(),
+            })
+        }
+
+        #[ink(message)]
+        pub fn freeze(&mut self, asset_id: u128, who: AccountId) -> Result<(), Error> {
+            self.call_assets(AssetsCall::Freeze {
+                id: asset_id,
+                who: who.into(),
+            })
+        }
+
+        #[ink(message)]
+        pub fn thaw(&mut self, asset_id: u128, who: AccountId) -> Result<(), Error> {
+            self.call_assets(AssetsCall::Thaw {
+                id: asset_id,
+                who: who.into(),
+            })
+        }
+
+        #[ink(message)]
+        pub fn start_destroy(&mut self, asset_id: u128) -> Result<(), Error> {
+            self.call_assets(AssetsCall::StartDestroy { id: asset_id })
+        }
+
+        #[ink(message)]
+        pub fn destroy_accounts(&mut self, asset_id: u128) -> Result<(), Error> {
+            self.call_assets(AssetsCall::DestroyAccounts { id: asset_id })
+        }
+
+        #[ink(message)]
+        pub fn destroy_approvals(&mut self, asset_id: u128) -> Result<(), Error> {
+            self.call_assets(AssetsCall::DestroyApprovals { id: asset_id })
+        }
+
+        #[ink(message)]
+        pub fn finish_destroy(&mut self, asset_id: u128) -> Result<(), Error> {
+            self.call_assets(AssetsCall::FinishDestroy { id: asset_id })
+        }
+
+        /// Execute `operations` in order, each one calling the extension once.
+        #[ink(message)]
+        pub fn batch(
+            &mut self,
+            operations: Vec<Operation>,
+            mode: BatchMode,
+        ) -> Result<Vec<Result<(), AssetsError>>, BatchError> {
+            let mut results = Vec::with_capacity(operations.len());
+            for (index, operation) in operations.into_iter().enumerate() {
+                let result = self.execute(operation);
+                if let (BatchMode::StopOnError, Err(error)) = (mode, result) {
+                    return Err(BatchError {
+                        index: index as u32,
+                        error,
+                    });
+                }
+                results.push(result);
+            }
+            Ok(results)
+        }
+
+        /// Dispatch `operation` to the extension as-is and return its error
+        /// unchanged, without emitting events. Used by the status code round-trip
+        /// tests: the caller picks the command and inputs which make `pallet-assets`
+        /// return the error under test, after setting up any preconditions with
+        /// `pallet-assets` extrinsics.
+        #[ink(message)]
+        pub fn trigger_error(&mut self, operation: Operation) -> Result<(), AssetsError> {
+            let assets = self.assets();
+            match operation {
+                Operation::Mint {
+                    asset_id,
+                    beneficiary,
+                    amount,
+                } => assets.mint(asset_id, beneficiary, amount),
+                Operation::Burn {
+                    asset_id,
+                    who,
+                    amount,
+                } => assets.burn(asset_id, who, amount),
+                Operation::Transfer {
+                    asset_id,
+                    target,
+                    amount,
+                } => assets.transfer(asset_id, target, amount),
+                Operation::ApproveTransfer {
+                    asset_id,
+                    delegate,
+                    amount,
+                } => assets.approve_transfer(asset_id, delegate, amount),
+                Operation::TransferApproved {
+                    asset_id,
+                    owner,
+                    destination,
+                    amount,
+                } => assets.transfer_approved(asset_id, owner, destination, amount),
+            }
+        }
+
         #[ink(message)]
         pub fn balance_of(&self, asset_id: u128, who: AccountId) -> Balance {
-            AssetsExtension::balance_of(asset_id, who)
+            self.assets().balance_of(asset_id, who)
         }
 
         #[ink(message)]
         pub fn total_supply(&self, asset_id: u128) -> Balance {
-            AssetsExtension::total_supply(asset_id)
+            self.assets().total_supply(asset_id)
         }
 
         #[ink(message)]
         pub fn allowance(&self, asset_id: u128, owner: AccountId, delegate: AccountId) -> Balance {
-            AssetsExtension::allowance(asset_id, owner, delegate)
+            self.assets().allowance(asset_id, owner, delegate)
         }
 
         #[ink(message)]
         pub fn metadata_name(&self, asset_id: u128) -> Vec<u8> {
-            AssetsExtension::metadata_name(asset_id)
+            self.assets().metadata_name(asset_id)
         }
 
         #[ink(message)]
         pub fn metadata_symbol(&self, asset_id: u128) -> Vec<u8> {
-            AssetsExtension::metadata_symbol(asset_id)
+            self.assets().metadata_symbol(asset_id)
         }
 
         #[ink(message)]
         pub fn metadata_decimals(&self, asset_id: u128) -> u8 {
-            AssetsExtension::metadata_decimals(asset_id)
+            self.assets().metadata_decimals(asset_id)
         }
 
         #[ink(message)]
         pub fn minimum_balance(&self, asset_id: u128) -> Balance {
-            AssetsExtension::minimum_balance(asset_id)
+            self.assets().minimum_balance(asset_id)
+        }
+
+        #[ink(message)]
+        pub fn extension_id(&self) -> u16 {
+            self.extension_id
+        }
+
+        fn call_assets(&self, call: AssetsCall) -> Result<(), Error> {
+            self.env()
+                .call_runtime(&RuntimeCall::Assets(call))
+                .map_err(|_| Error::CallRuntimeFailed)
+        }
+
+        fn assets(&self) -> AssetsExtension {
+            AssetsExtension::new_with_extension_id(self.extension_id)
+        }
+
+        fn execute(&mut self, operation: Operation) -> Result<(), AssetsError> {
+            match operation {
+                Operation::Mint {
+                    asset_id,
+                    beneficiary,
+                    amount,
+                } => self.mint(asset_id, beneficiary, amount),
+                Operation::Burn {
+                    asset_id,
+                    who,
+                    amount,
+                } => self.burn(asset_id, who, amount),
+                Operation::Transfer {
+                    asset_id,
+                    target,
+                    amount,
+                } => self.transfer(asset_id, target, amount),
+                Operation::ApproveTransfer {
+                    asset_id,
+                    delegate,
+                    amount,
+                } => self.approve_transfer(asset_id, delegate, amount),
+                Operation::TransferApproved {
+                    asset_id,
+                    owner,
+                    destination,
+                    amount,
+                } => self.transfer_approved(asset_id, owner, destination, amount),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::ledger;
+        use assets_chain_extension_types::Command;
+        use chain_extension_mock::MockExtension;
+        use ink::env::test::{callee, default_accounts, recorded_events, set_caller};
+        use scale::{Decode, Encode};
+
+        const ASSET_ID: u128 = 1;
+
+        /// Assets chain extension, registered under its default ID
+        const ASSETS: MockExtension = MockExtension::new(2);
+
+        /// All the errors that can be returned by the extension through status code
+        const ALL_ERRORS: [AssetsError; 21] = [
+            AssetsError::BalanceLow,
+            AssetsError::NoAccount,
+            AssetsError::NoPermission,
+            AssetsError::Unknown,
+            AssetsError::Frozen,
+            AssetsError::InUse,
+            AssetsError::BadWitness,
+            AssetsError::MinBalanceZero,
+            AssetsError::UnavailableConsumer,
+            AssetsError::BadMetadata,
+            AssetsError::Unapproved,
+            AssetsError::WouldDie,
+            AssetsError::AlreadyExists,
+            AssetsError::NoDeposit,
+            AssetsError::WouldBurn,
+            AssetsError::LiveAsset,
+            AssetsError::AssetNotLive,
+            AssetsError::IncorrectStatus,
+            AssetsError::NotFrozen,
+            AssetsError::CallbackFailed,
+            AssetsError::RuntimeError,
+        ];
+
+        /// Assert that every status code returned for `command` is mapped to the
+        /// corresponding error by `call`.
+        fn assert_error_codes(
+            command: fn() -> Command,
+            mut call: impl FnMut() -> Result<(), AssetsError>,
+        ) {
+            for err in ALL_ERRORS {
+                ASSETS.register_status_code(command(), err as u32);
+                assert_eq!(call(), Err(err));
+            }
+            ASSETS.register_status_code(command(), 21);
+            assert_eq!(call(), Err(AssetsError::UnknownStatusCode));
+            assert_eq!(recorded_events().count(), 0);
+        }
+
+        #[ink::test]
+        fn mint_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register(Command::Mint, move |input: (u128, AccountId, Balance)| {
+                assert_eq!(input, (ASSET_ID, bob, 100));
+                (0, ())
+            });
+
+            assert_eq!(Mock::new().mint(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn mint_returns_errors() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            assert_error_codes(|| Command::Mint, || Mock::new().mint(ASSET_ID, bob, 100));
+        }
+
+        #[ink::test]
+        fn burn_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register(Command::Burn, move |input: (u128, AccountId, Balance)| {
+                assert_eq!(input, (ASSET_ID, bob, 100));
+                (0, ())
+            });
+
+            assert_eq!(Mock::new().burn(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn burn_returns_errors() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            assert_error_codes(|| Command::Burn, || Mock::new().burn(ASSET_ID, bob, 100));
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register(
+                Command::Transfer,
+                move |input: (u128, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, bob, 100));
+                    (0, ())
+                },
+            );
+
+            assert_eq!(Mock::new().transfer(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn events_are_indexed_by_asset_and_accounts() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            ASSETS.register_status_code(Command::Transfer, 0);
+            ASSETS.register_status_code(Command::TransferApproved, 0);
+
+            let mut contract = Mock::new();
+            contract.transfer(ASSET_ID, accounts.bob, 100).unwrap();
+            contract
+                .transfer_approved(ASSET_ID, accounts.bob, accounts.charlie, 100)
+                .unwrap();
+
+            // event signature, asset id, owner & destination
+            assert!(recorded_events().all(|event| event.topics.len() == 4));
+        }
+
+        #[ink::test]
+        fn transfer_returns_errors() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            assert_error_codes(
+                || Command::Transfer,
+                || Mock::new().transfer(ASSET_ID, bob, 100),
+            );
+        }
+
+        #[ink::test]
+        fn approve_transfer_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register(
+                Command::ApproveTransfer,
+                move |input: (u128, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, bob, 100));
+                    (0, ())
+                },
+            );
+
+            assert_eq!(Mock::new().approve_transfer(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn approve_transfer_returns_errors() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            assert_error_codes(
+                || Command::ApproveTransfer,
+                || Mock::new().approve_transfer(ASSET_ID, bob, 100),
+            );
+        }
+
+        #[ink::test]
+        fn transfer_approved_works() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            ASSETS.register(
+                Command::TransferApproved,
+                move |input: (u128, AccountId, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, bob, charlie, 100));
+                    (0, ())
+                },
+            );
+
+            assert_eq!(
+                Mock::new().transfer_approved(ASSET_ID, bob, charlie, 100),
+                Ok(())
+            );
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_approved_returns_errors() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            assert_error_codes(
+                || Command::TransferApproved,
+                || Mock::new().transfer_approved(ASSET_ID, accounts.bob, accounts.charlie, 100),
+            );
+        }
+
+        #[ink::test]
+        fn transfer_as_works() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            set_caller::<DefaultEnvironment>(bob);
+            ASSETS.register(
+                Command::Transfer,
+                move |input: (u128, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, charlie, 100));
+                    (0, ())
+                },
+            );
+            ASSETS.register(
+                Command::TransferApproved,
+                move |input: (u128, AccountId, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, bob, charlie, 100));
+                    (0, ())
+                },
+            );
+
+            let mut contract = Mock::new();
+            assert_eq!(
+                contract.transfer_as(Origin::Contract, ASSET_ID, charlie, 100),
+                Ok(())
+            );
+            assert_eq!(
+                contract.transfer_as(Origin::Caller, ASSET_ID, charlie, 100),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn transfer_as_errors() {
+            let charlie = default_accounts::<DefaultEnvironment>().charlie;
+            let mut contract = Mock::new();
+            for origin in [Origin::Contract, Origin::Caller] {
+                for err in [AssetsError::NoPermission, AssetsError::Unapproved] {
+                    match origin {
+                        Origin::Contract => {
+                            ASSETS.register_status_code(Command::Transfer, err as u32)
+                        }
+                        Origin::Caller => {
+                            ASSETS.register_status_code(Command::TransferApproved, err as u32)
+                        }
+                    }
+                    assert_eq!(
+                        contract.transfer_as(origin, ASSET_ID, charlie, 100),
+                        Err(Error::Assets(err))
+                    );
+                }
+            }
+        }
+
+        #[ink::test]
+        fn transfer_approved_as_works() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            ASSETS.register(
+                Command::TransferApproved,
+                move |input: (u128, AccountId, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, accounts.bob, accounts.charlie, 100));
+                    (0, ())
+                },
+            );
+
+            assert_eq!(
+                Mock::new().transfer_approved_as(
+                    Origin::Contract,
+                    ASSET_ID,
+                    accounts.bob,
+                    accounts.charlie,
+                    100
+                ),
+                Ok(())
+            );
+        }
+
+        #[ink::test]
+        fn transfer_approved_as_errors() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            let mut contract = Mock::new();
+            for err in [AssetsError::NoPermission, AssetsError::Unapproved] {
+                ASSETS.register_status_code(Command::TransferApproved, err as u32);
+                assert_eq!(
+                    contract.transfer_approved_as(
+                        Origin::Contract,
+                        ASSET_ID,
+                        accounts.django,
+                        accounts.charlie,
+                        100
+                    ),
+                    Err(Error::Assets(err))
+                );
+            }
+
+            ASSETS.register(
+                Command::TransferApproved,
+                |_: (u128, AccountId, AccountId, Balance)| -> (u32, ()) {
+                    panic!("extension must not be called for Origin::Caller")
+                },
+            );
+            assert_eq!(
+                contract.transfer_approved_as(
+                    Origin::Caller,
+                    ASSET_ID,
+                    accounts.bob,
+                    accounts.charlie,
+                    100
+                ),
+                Err(Error::CallerOriginUnsupported)
+            );
+        }
+
+        #[ink::test]
+        fn burn_as_works() {
+            let contract_account = callee::<DefaultEnvironment>();
+            ASSETS.register(Command::Burn, move |input: (u128, AccountId, Balance)| {
+                assert_eq!(input, (ASSET_ID, contract_account, 100));
+                (0, ())
+            });
+
+            let mut contract = Mock::new();
+            assert_eq!(contract.burn_as(Origin::Contract, ASSET_ID, 100), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn burn_as_errors() {
+            let mut contract = Mock::new();
+            for err in [AssetsError::NoPermission, AssetsError::Unapproved] {
+                ASSETS.register_status_code(Command::Burn, err as u32);
+                assert_eq!(
+                    contract.burn_as(Origin::Contract, ASSET_ID, 100),
+                    Err(Error::Assets(err))
+                );
+            }
+
+            ASSETS.register(
+                Command::Burn,
+                |_: (u128, AccountId, Balance)| -> (u32, ()) {
+                    panic!("extension must not be called for Origin::Caller")
+                },
+            );
+            assert_eq!(
+                contract.burn_as(Origin::Caller, ASSET_ID, 100),
+                Err(Error::CallerOriginUnsupported)
+            );
+        }
+
+        #[ink::test]
+        fn lifecycle_calls_are_encoded_for_assets_pallet() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let encoded = |call: AssetsCall| RuntimeCall::Assets(call).encode();
+            // pallet index 36, call index, then `Compact<u128>` asset ID 1
+            let prefix = |call_index: u8| vec![36, call_index, 0x04];
+            let with = |call_index: u8, args: &[&[u8]]| {
+                let mut bytes = prefix(call_index);
+                args.iter().for_each(|arg| bytes.extend(*arg));
+                bytes
+            };
+            // `MultiAddress::Id(bob)`
+            let address = [&[0][..], bob.as_ref()].concat();
+
+            assert_eq!(
+                encoded(AssetsCall::Create {
+                    id: ASSET_ID,
+                    admin: bob.into(),
+                    min_balance: 10,
+                }),
+                with(0, &[&address, &10u128.to_le_bytes()])
+            );
+            assert_eq!(
+                encoded(AssetsCall::StartDestroy { id: ASSET_ID }),
+                prefix(2)
+            );
+            assert_eq!(
+                encoded(AssetsCall::DestroyAccounts { id: ASSET_ID }),
+                prefix(3)
+            );
+            assert_eq!(
+                encoded(AssetsCall::DestroyApprovals { id: ASSET_ID }),
+                prefix(4)
+            );
+            assert_eq!(
+                encoded(AssetsCall::FinishDestroy { id: ASSET_ID }),
+                prefix(5)
+            );
+            assert_eq!(
+                encoded(AssetsCall::Freeze {
+                    id: ASSET_ID,
+                    who: bob.into(),
+                }),
+                with(11, &[&address])
+            );
+            assert_eq!(
+                encoded(AssetsCall::Thaw {
+                    id: ASSET_ID,
+                    who: bob.into(),
+                }),
+                with(12, &[&address])
+            );
+            assert_eq!(
+                encoded(AssetsCall::SetMetadata {
+                    id: ASSET_ID,
+                    name: b"Token".to_vec(),
+                    symbol: b"TKN".to_vec(),
+                    decimals: 18,
+                }),
+                with(17, &[&[5 << 2], b"Token", &[3 << 2], b"TKN", &[18]])
+            );
+            assert_eq!(
+                encoded(AssetsCall::CancelApproval {
+                    id: ASSET_ID,
+                    delegate: bob.into(),
+                }),
+                with(23, &[&address])
+            );
+        }
+
+        #[ink::test]
+        fn balance_of_works() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register(Command::BalanceOf, move |input: (u128, AccountId)| {
+                assert_eq!(input, (ASSET_ID, bob));
+                (0, 100 as Balance)
+            });
+
+            assert_eq!(Mock::new().balance_of(ASSET_ID, bob), 100);
+        }
+
+        #[ink::test]
+        fn total_supply_works() {
+            ASSETS.register(Command::TotalSupply, |id: u128| {
+                assert_eq!(id, ASSET_ID);
+                (0, 1000 as Balance)
+            });
+
+            assert_eq!(Mock::new().total_supply(ASSET_ID), 1000);
+        }
+
+        #[ink::test]
+        fn allowance_works() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            ASSETS.register(
+                Command::Allowance,
+                move |input: (u128, AccountId, AccountId)| {
+                    assert_eq!(input, (ASSET_ID, bob, charlie));
+                    (0, 10 as Balance)
+                },
+            );
+
+            assert_eq!(Mock::new().allowance(ASSET_ID, bob, charlie), 10);
+        }
+
+        #[ink::test]
+        fn metadata_works() {
+            ASSETS.register(Command::MetadataName, |_: u128| (0, b"Token".to_vec()));
+            ASSETS.register(Command::MetadataSymbol, |_: u128| (0, b"TKN".to_vec()));
+            ASSETS.register(Command::MetadataDecimals, |_: u128| (0, 18u8));
+
+            let contract = Mock::new();
+            assert_eq!(contract.metadata_name(ASSET_ID), b"Token".to_vec());
+            assert_eq!(contract.metadata_symbol(ASSET_ID), b"TKN".to_vec());
+            assert_eq!(contract.metadata_decimals(ASSET_ID), 18);
+        }
+
+        #[ink::test]
+        fn minimum_balance_works() {
+            ASSETS.register(Command::MinimumBalance, |id: u128| {
+                assert_eq!(id, ASSET_ID);
+                (0, 1 as Balance)
+            });
+
+            assert_eq!(Mock::new().minimum_balance(ASSET_ID), 1);
+        }
+
+        #[ink::test]
+        fn error_discriminants_match_status_codes() {
+            use ink::env::chain_extension::FromStatusCode;
+
+            assert_eq!(AssetsError::from_status_code(0), Ok(()));
+            for err in ALL_ERRORS {
+                assert_eq!(AssetsError::from_status_code(err as u32), Err(err));
+            }
+            for code in (21..99).chain(100..=u8::MAX as u32).chain([u32::MAX]) {
+                assert_eq!(
+                    AssetsError::from_status_code(code),
+                    Err(AssetsError::UnknownStatusCode)
+                );
+            }
+        }
+
+        #[ink::test]
+        fn error_encoding_matches_discriminants() {
+            for err in ALL_ERRORS {
+                let encoded = err.encode();
+                assert_eq!(encoded, vec![err as u8]);
+                assert_eq!(AssetsError::decode(&mut &encoded[..]), Ok(err));
+            }
+            // errors raised on the contract side are encoded by their position
+            for (err, index) in [
+                (AssetsError::UnknownStatusCode, 21),
+                (AssetsError::InvalidScaleEncoding, 22),
+            ] {
+                let encoded = err.encode();
+                assert_eq!(encoded, vec![index]);
+                assert_eq!(AssetsError::decode(&mut &encoded[..]), Ok(err));
+            }
+        }
+
+        #[ink::test]
+        fn trigger_error_passes_operations_through() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            let mut contract = Mock::new();
+            let operations: [(fn() -> Command, Operation); 5] = [
+                (
+                    || Command::Mint,
+                    Operation::Mint {
+                        asset_id: ASSET_ID,
+                        beneficiary: bob,
+                        amount: 100,
+                    },
+                ),
+                (
+                    || Command::Burn,
+                    Operation::Burn {
+                        asset_id: ASSET_ID,
+                        who: bob,
+                        amount: 100,
+                    },
+                ),
+                (
+                    || Command::Transfer,
+                    Operation::Transfer {
+                        asset_id: ASSET_ID,
+                        target: bob,
+                        amount: 100,
+                    },
+                ),
+                (
+                    || Command::ApproveTransfer,
+                    Operation::ApproveTransfer {
+                        asset_id: ASSET_ID,
+                        delegate: bob,
+                        amount: 100,
+                    },
+                ),
+                (
+                    || Command::TransferApproved,
+                    Operation::TransferApproved {
+                        asset_id: ASSET_ID,
+                        owner: bob,
+                        destination: charlie,
+                        amount: 100,
+                    },
+                ),
+            ];
+
+            for (command, operation) in operations {
+                assert_error_codes(command, || contract.trigger_error(operation.clone()));
+            }
+        }
+
+        #[ink::test]
+        fn calls_use_configured_extension_id() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register_status_code(Command::Mint, 0);
+
+            let mut contract = Mock::new();
+            assert_eq!(contract.extension_id(), ASSETS.id());
+            assert_eq!(contract.mint(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(Mock::new_with_extension_id(7).extension_id(), 7);
+        }
+
+        #[ink::test]
+        #[should_panic(expected = "missing chain extension method")]
+        fn calls_to_unregistered_extension_id_fail() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register_status_code(Command::Mint, 0);
+
+            let _ = Mock::new_with_extension_id(ASSETS.id() + 1).mint(ASSET_ID, bob, 100);
+        }
+
+        fn batch_operations(bob: AccountId) -> Vec<Operation> {
+            vec![
+                Operation::Mint {
+                    asset_id: ASSET_ID,
+                    beneficiary: callee::<DefaultEnvironment>(),
+                    amount: 100,
+                },
+                Operation::Transfer {
+                    asset_id: ASSET_ID,
+                    target: bob,
+                    amount: 1000,
+                },
+                Operation::Burn {
+                    asset_id: ASSET_ID,
+                    who: bob,
+                    amount: 10,
+                },
+            ]
+        }
+
+        #[ink::test]
+        fn batch_stops_on_first_error() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register_status_code(Command::Mint, 0);
+            ASSETS.register_status_code(Command::Transfer, AssetsError::BalanceLow as u32);
+            ASSETS.register(
+                Command::Burn,
+                |_: (u128, AccountId, Balance)| -> (u32, ()) {
+                    panic!("operations after the failed one must not be executed")
+                },
+            );
+
+            assert_eq!(
+                Mock::new().batch(batch_operations(bob), BatchMode::StopOnError),
+                Err(BatchError {
+                    index: 1,
+                    error: AssetsError::BalanceLow
+                })
+            );
+        }
+
+        #[ink::test]
+        fn batch_collects_results() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            ASSETS.register_status_code(Command::Mint, 0);
+            ASSETS.register_status_code(Command::Transfer, AssetsError::BalanceLow as u32);
+            ASSETS.register_status_code(Command::Burn, 0);
+
+            assert_eq!(
+                Mock::new().batch(batch_operations(bob), BatchMode::CollectResults),
+                Ok(vec![Ok(()), Err(AssetsError::BalanceLow), Ok(())])
+            );
+            // only the successful operations emit events
+            assert_eq!(recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn empty_batch_works() {
+            let mut contract = Mock::new();
+            assert_eq!(contract.batch(vec![], BatchMode::StopOnError), Ok(vec![]));
+            assert_eq!(
+                contract.batch(vec![], BatchMode::CollectResults),
+                Ok(vec![])
+            );
+        }
+
+        #[ink::test]
+        fn emulated_assets_work() {
+            let contract_id = callee::<DefaultEnvironment>();
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let ledger = ledger::register(ASSETS, contract_id);
+            let mut contract = Mock::new();
+            ledger
+                .borrow_mut()
+                .create(contract_id, ASSET_ID, contract_id, 10)
+                .unwrap();
+            ledger
+                .borrow_mut()
+                .set_metadata(
+                    contract_id,
+                    ASSET_ID,
+                    b"Token".to_vec(),
+                    b"TKN".to_vec(),
+                    18,
+                )
+                .unwrap();
+
+            assert_eq!(contract.minimum_balance(ASSET_ID), 10);
+            assert_eq!(contract.mint(ASSET_ID, contract_id, 1000), Ok(()));
+            assert_eq!(contract.transfer(ASSET_ID, bob, 100), Ok(()));
+            assert_eq!(contract.burn(ASSET_ID, bob, 50), Ok(()));
+            assert_eq!(contract.balance_of(ASSET_ID, bob), 50);
+            assert_eq!(contract.total_supply(ASSET_ID), 950);
+            assert_eq!(contract.metadata_name(ASSET_ID), b"Token".to_vec());
+            assert_eq!(contract.metadata_decimals(ASSET_ID), 18);
+            assert_eq!(contract.mint(2, bob, 10), Err(AssetsError::Unknown));
+
+            ledger
+                .borrow_mut()
+                .start_destroy(contract_id, ASSET_ID)
+                .unwrap();
+            assert_eq!(
+                contract.mint(ASSET_ID, bob, 10),
+                Err(AssetsError::AssetNotLive)
+            );
+            assert_eq!(
+                contract.transfer(ASSET_ID, bob, 10),
+                Err(AssetsError::AssetNotLive)
+            );
+        }
+
+        #[ink::test]
+        fn emulated_overflow_is_rejected() {
+            let contract_id = callee::<DefaultEnvironment>();
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let ledger = ledger::register(ASSETS, contract_id);
+            let mut contract = Mock::new();
+            ledger
+                .borrow_mut()
+                .create(contract_id, ASSET_ID, contract_id, 1)
+                .unwrap();
+
+            assert_eq!(contract.mint(ASSET_ID, contract_id, Balance::MAX), Ok(()));
+            // supply overflows for any holder, nothing is credited
+            for who in [bob, contract_id] {
+                assert_eq!(
+                    contract.mint(ASSET_ID, who, 1),
+                    Err(AssetsError::RuntimeError)
+                );
+            }
+            assert_eq!(contract.balance_of(ASSET_ID, bob), 0);
+            assert_eq!(contract.balance_of(ASSET_ID, contract_id), Balance::MAX);
+            assert_eq!(contract.total_supply(ASSET_ID), Balance::MAX);
+        }
+
+        #[ink::test]
+        fn emulated_minimum_balance_is_enforced() {
+            let contract_id = callee::<DefaultEnvironment>();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let ledger = ledger::register(ASSETS, contract_id);
+            let mut contract = Mock::new();
+            ledger
+                .borrow_mut()
+                .create(contract_id, ASSET_ID, contract_id, 10)
+                .unwrap();
+            contract.mint(ASSET_ID, contract_id, 100).unwrap();
+
+            // new accounts must receive at least the minimum balance
+            assert_eq!(
+                contract.transfer(ASSET_ID, accounts.bob, 9),
+                Err(AssetsError::RuntimeError)
+            );
+            assert_eq!(contract.transfer(ASSET_ID, accounts.bob, 10), Ok(()));
+            // dust below the minimum balance is moved along with the transfer
+            assert_eq!(contract.transfer(ASSET_ID, accounts.charlie, 85), Ok(()));
+            assert_eq!(contract.balance_of(ASSET_ID, contract_id), 0);
+            assert_eq!(contract.balance_of(ASSET_ID, accounts.charlie), 90);
+            // and burned along with the burn
+            assert_eq!(contract.burn(ASSET_ID, accounts.bob, 5), Ok(()));
+            assert_eq!(contract.balance_of(ASSET_ID, accounts.bob), 0);
+            assert_eq!(contract.total_supply(ASSET_ID), 90);
+        }
+
+        #[ink::test]
+        fn emulated_approvals_and_frozen_accounts_are_enforced() {
+            let contract_id = callee::<DefaultEnvironment>();
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            let ledger = ledger::register(ASSETS, contract_id);
+            let mut contract = Mock::new();
+            ledger
+                .borrow_mut()
+                .create(contract_id, ASSET_ID, contract_id, 1)
+                .unwrap();
+            contract.mint(ASSET_ID, bob, 100).unwrap();
+
+            assert_eq!(
+                contract.transfer_approved(ASSET_ID, bob, charlie, 10),
+                Err(AssetsError::Unapproved)
+            );
+            ledger
+                .borrow_mut()
+                .approve_transfer(bob, ASSET_ID, contract_id, 50)
+                .unwrap();
+            assert_eq!(contract.allowance(ASSET_ID, bob, contract_id), 50);
+            assert_eq!(
+                contract.transfer_approved(ASSET_ID, bob, charlie, 60),
+                Err(AssetsError::Unapproved)
+            );
+            assert_eq!(
+                contract.transfer_approved(ASSET_ID, bob, charlie, 30),
+                Ok(())
+            );
+            assert_eq!(contract.allowance(ASSET_ID, bob, contract_id), 20);
+
+            ledger
+                .borrow_mut()
+                .freeze(contract_id, ASSET_ID, bob)
+                .unwrap();
+            assert!(ledger.borrow().is_frozen(ASSET_ID, bob));
+            assert_eq!(
+                contract.transfer_approved(ASSET_ID, bob, charlie, 10),
+                Err(AssetsError::Frozen)
+            );
+            ledger
+                .borrow_mut()
+                .thaw(contract_id, ASSET_ID, bob)
+                .unwrap();
+            assert_eq!(
+                contract.transfer_approved(ASSET_ID, bob, charlie, 10),
+                Ok(())
+            );
+            assert_eq!(contract.balance_of(ASSET_ID, charlie), 40);
+        }
+
+        #[ink::test]
+        fn trigger_error_matches_emulated_pallet() {
+            let contract_id = callee::<DefaultEnvironment>();
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let ledger = ledger::register(ASSETS, contract_id);
+            let mut contract = Mock::new();
+            // contract owns the asset but `bob` is the issuer & freezer
+            ledger
+                .borrow_mut()
+                .create(contract_id, ASSET_ID, bob, 1)
+                .unwrap();
+            let mint = |asset_id: u128| Operation::Mint {
+                asset_id,
+                beneficiary: bob,
+                amount: 1,
+            };
+            let transfer = |amount: Balance| Operation::Transfer {
+                asset_id: ASSET_ID,
+                target: bob,
+                amount,
+            };
+            let mut assert_error = |operation: Operation, err: AssetsError| {
+                assert_eq!(contract.trigger_error(operation), Err(err));
+            };
+
+            assert_error(mint(ASSET_ID), AssetsError::NoPermission);
+            assert_error(mint(2), AssetsError::Unknown);
+            assert_error(transfer(1), AssetsError::NoAccount);
+            assert_error(
+                Operation::TransferApproved {
+                    asset_id: ASSET_ID,
+                    owner: bob,
+                    destination: contract_id,
+                    amount: 1,
+                },
+                AssetsError::Unapproved,
+            );
+
+            ledger
+                .borrow_mut()
+                .mint(bob, ASSET_ID, contract_id, 10)
+                .unwrap();
+            ledger
+                .borrow_mut()
+                .freeze(bob, ASSET_ID, contract_id)
+                .unwrap();
+            assert_error(transfer(1), AssetsError::Frozen);
+            ledger
+                .borrow_mut()
+                .thaw(bob, ASSET_ID, contract_id)
+                .unwrap();
+            assert_error(transfer(Balance::MAX), AssetsError::BalanceLow);
+
+            ledger
+                .borrow_mut()
+                .start_destroy(contract_id, ASSET_ID)
+                .unwrap();
+            assert_error(mint(ASSET_ID), AssetsError::AssetNotLive);
         }
     }
-}
\ No newline at end of file
+}
@@ -22,16 +22,273 @@
 
 #[ink::contract]
 mod payable {
+    use ink::storage::Mapping;
+
     #[ink(storage)]
-    pub struct Payable {}
-    
+    pub struct Payable {
+        owner: AccountId,
+        /// Value deposited by each caller and not yet withdrawn
+        deposits: Mapping<AccountId, Balance>,
+        /// Sum of all the `deposits`
+        total_deposits: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Deposited {
+        #[ink(topic)]
+        from: AccountId,
+        amount: Balance,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Caller is not the owner
+        NotOwner,
+        /// Transfer failed, e.g. insufficient balance or below existential deposit
+        TransferFailed,
+        /// Caller has nothing deposited
+        NoDeposit,
+        /// Amount exceeds the balance which is not owed to depositors
+        InsufficientFreeBalance,
+        /// Contract can't be terminated while depositors have not withdrawn
+        DepositsOutstanding,
+    }
+
     impl Payable {
         #[ink(constructor)]
         pub fn new() -> Self {
-            Self {}
+            Self {
+                owner: Self::env().caller(),
+                deposits: Mapping::default(),
+                total_deposits: 0,
+            }
         }
-        
+
+        /// Same as `new`, but accepts value.
+        #[ink(constructor, payable)]
+        pub fn new_payable() -> Self {
+            Self::new()
+        }
+
+        /// Record the transferred value as caller's deposit, zero value is ignored.
         #[ink(message, payable, selector = 42)]
-        pub fn deposit(&self) {}
+        pub fn deposit(&mut self) {
+            let from = self.env().caller();
+            let amount = self.env().transferred_value();
+            if amount == 0 {
+                return;
+            }
+            let deposited = self.deposit_of(from).saturating_add(amount);
+            self.deposits.insert(from, &deposited);
+            self.total_deposits = self.total_deposits.saturating_add(amount);
+            self.env().emit_event(Deposited { from, amount });
+        }
+
+        /// Transfer `amount` of contract's balance to `to`. Only callable by the owner,
+        /// the deposits are kept for their withdrawal.
+        #[ink(message, selector = 43)]
+        pub fn transfer(&mut self, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if amount > self.env().balance().saturating_sub(self.total_deposits) {
+                return Err(Error::InsufficientFreeBalance);
+            }
+            self.env()
+                .transfer(to, amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        /// Terminate the contract, sending the remaining balance to `beneficiary`.
+        /// Only callable by the owner once all the deposits are withdrawn.
+        #[ink(message, selector = 44)]
+        pub fn terminate_contract(&mut self, beneficiary: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if self.total_deposits > 0 {
+                return Err(Error::DepositsOutstanding);
+            }
+            self.env().terminate_contract(beneficiary)
+        }
+
+        #[ink(message, selector = 45)]
+        pub fn balance(&self) -> Balance {
+            self.env().balance()
+        }
+
+        /// Send back everything the caller has deposited.
+        #[ink(message, selector = 46)]
+        pub fn withdraw_my_deposit(&mut self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            let amount = self.deposits.take(caller).ok_or(Error::NoDeposit)?;
+            self.total_deposits -= amount;
+            self.env()
+                .transfer(caller, amount)
+                .map_err(|_| Error::TransferFailed)
+        }
+
+        #[ink(message, selector = 47)]
+        pub fn deposit_of(&self, who: AccountId) -> Balance {
+            self.deposits.get(who).unwrap_or_default()
+        }
+
+        /// Returns the transferred value, same as `reject_value` but payable.
+        #[ink(message, payable, selector = 48)]
+        pub fn accept_value(&self) -> Balance {
+            self.env().transferred_value()
+        }
+
+        /// Returns the transferred value, calls with non-zero value are rejected.
+        #[ink(message, selector = 49)]
+        pub fn reject_value(&self) -> Balance {
+            self.env().transferred_value()
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test, DefaultEnvironment};
+
+        /// Deposit `amount` as `from`, crediting the contract's balance with the value.
+        fn deposit_as(contract: &mut Payable, from: AccountId, amount: Balance) {
+            let contract_id = test::callee::<DefaultEnvironment>();
+            let balance = test::get_account_balance::<DefaultEnvironment>(contract_id)
+                .expect("contract has a balance");
+            test::set_account_balance::<DefaultEnvironment>(contract_id, balance + amount);
+            test::set_caller::<DefaultEnvironment>(from);
+            test::set_value_transferred::<DefaultEnvironment>(amount);
+            contract.deposit();
+            test::set_value_transferred::<DefaultEnvironment>(0);
+        }
+
+        fn setup() -> Payable {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::set_account_balance::<DefaultEnvironment>(
+                test::callee::<DefaultEnvironment>(),
+                0,
+            );
+            test::set_account_balance::<DefaultEnvironment>(accounts.bob, 0);
+            Payable::new()
+        }
+
+        #[ink::test]
+        fn deposit_works() {
+            let bob = test::default_accounts::<DefaultEnvironment>().bob;
+            let mut contract = setup();
+
+            deposit_as(&mut contract, bob, 100);
+            deposit_as(&mut contract, bob, 50);
+            assert_eq!(contract.deposit_of(bob), 150);
+            assert_eq!(contract.balance(), 150);
+            assert_eq!(test::recorded_events().count(), 2);
+        }
+
+        #[ink::test]
+        fn zero_deposit_is_ignored() {
+            let bob = test::default_accounts::<DefaultEnvironment>().bob;
+            let mut contract = setup();
+
+            deposit_as(&mut contract, bob, 0);
+            assert_eq!(contract.deposits.get(bob), None);
+            assert_eq!(test::recorded_events().count(), 0);
+            assert_eq!(contract.withdraw_my_deposit(), Err(Error::NoDeposit));
+        }
+
+        #[ink::test]
+        fn withdraw_works() {
+            let bob = test::default_accounts::<DefaultEnvironment>().bob;
+            let mut contract = setup();
+            deposit_as(&mut contract, bob, 100);
+
+            assert_eq!(contract.withdraw_my_deposit(), Ok(()));
+            assert_eq!(contract.deposit_of(bob), 0);
+            assert_eq!(contract.balance(), 0);
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(bob),
+                Ok(100)
+            );
+        }
+
+        #[ink::test]
+        fn double_withdraw_fails() {
+            let bob = test::default_accounts::<DefaultEnvironment>().bob;
+            let mut contract = setup();
+            deposit_as(&mut contract, bob, 100);
+
+            assert_eq!(contract.withdraw_my_deposit(), Ok(()));
+            assert_eq!(contract.withdraw_my_deposit(), Err(Error::NoDeposit));
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(bob),
+                Ok(100)
+            );
+        }
+
+        #[ink::test]
+        fn owner_transfer_keeps_deposits() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut contract = setup();
+            deposit_as(&mut contract, accounts.bob, 100);
+            // value sent to the contract without a deposit
+            test::set_account_balance::<DefaultEnvironment>(
+                test::callee::<DefaultEnvironment>(),
+                130,
+            );
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.transfer(accounts.bob, 10), Err(Error::NotOwner));
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.transfer(accounts.charlie, 31),
+                Err(Error::InsufficientFreeBalance)
+            );
+            assert_eq!(contract.transfer(accounts.charlie, 30), Ok(()));
+
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.withdraw_my_deposit(), Ok(()));
+            assert_eq!(
+                test::get_account_balance::<DefaultEnvironment>(accounts.bob),
+                Ok(100)
+            );
+        }
+
+        #[ink::test]
+        fn terminate_requires_withdrawn_deposits() {
+            let accounts = test::default_accounts::<DefaultEnvironment>();
+            let mut contract = setup();
+            deposit_as(&mut contract, accounts.bob, 100);
+            // value sent to the contract without a deposit
+            test::set_account_balance::<DefaultEnvironment>(
+                test::callee::<DefaultEnvironment>(),
+                130,
+            );
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.terminate_contract(accounts.django),
+                Err(Error::DepositsOutstanding)
+            );
+            test::set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.terminate_contract(accounts.django),
+                Err(Error::NotOwner)
+            );
+            assert_eq!(contract.withdraw_my_deposit(), Ok(()));
+
+            test::set_caller::<DefaultEnvironment>(accounts.alice);
+            test::assert_contract_termination::<DefaultEnvironment, _>(
+                move || {
+                    let _ = contract.terminate_contract(accounts.django);
+                },
+                accounts.django,
+                30,
+            );
+        }
     }
-}
\ No newline at end of file
+}
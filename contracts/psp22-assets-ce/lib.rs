@@ -0,0 +1,523 @@
+// This file is part of Astar.
+
+// Copyright (C) 2019-2023 Stake Technologies Pte.Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// Astar is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Astar is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Astar. If not, see <http://www.gnu.org/licenses/>.
+
+//! PSP22 token backed by a single `pallet-assets` asset.
+//!
+//! The assets chain extension acts with the contract as origin, so holders must
+//! approve this contract in `pallet-assets` (`approve_transfer`) for at least the
+//! amount they want to move. Transfers are then performed by the contract using
+//! `transfer_approved`, while PSP22 allowances between holders and spenders are
+//! tracked by the contract itself.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+use assets_extension::{AssetsError, AssetsExtension as _AssetsExtension};
+use ink::env::DefaultEnvironment;
+use ink::prelude::{string::String, vec::Vec};
+use ink::primitives::AccountId;
+
+type AssetsExtension = _AssetsExtension<DefaultEnvironment>;
+
+#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum PSP22Error {
+    /// Custom error type for implementation-based errors.
+    Custom(String),
+    /// Returned when an account does not have enough tokens to complete the operation.
+    InsufficientBalance,
+    /// Returned if there is not enough allowance to complete the operation.
+    InsufficientAllowance,
+    /// Returned if recipient's address is zero.
+    ZeroRecipientAddress,
+    /// Returned if sender's address is zero.
+    ZeroSenderAddress,
+    /// Returned if a safe transfer check failed.
+    SafeTransferCheckFailed(String),
+}
+
+impl From<AssetsError> for PSP22Error {
+    fn from(error: AssetsError) -> Self {
+        match error {
+            AssetsError::BalanceLow => PSP22Error::InsufficientBalance,
+            AssetsError::Unapproved => PSP22Error::InsufficientAllowance,
+            AssetsError::NoAccount => PSP22Error::Custom(String::from("NoAccount")),
+            AssetsError::NoPermission => PSP22Error::Custom(String::from("NoPermission")),
+            AssetsError::Unknown => PSP22Error::Custom(String::from("Unknown")),
+            AssetsError::Frozen => PSP22Error::Custom(String::from("Frozen")),
+            AssetsError::InUse => PSP22Error::Custom(String::from("InUse")),
+            AssetsError::BadWitness => PSP22Error::Custom(String::from("BadWitness")),
+            AssetsError::MinBalanceZero => PSP22Error::Custom(String::from("MinBalanceZero")),
+            AssetsError::UnavailableConsumer => {
+                PSP22Error::Custom(String::from("UnavailableConsumer"))
+            }
+            AssetsError::BadMetadata => PSP22Error::Custom(String::from("BadMetadata")),
+            AssetsError::WouldDie => PSP22Error::Custom(String::from("WouldDie")),
+            AssetsError::AlreadyExists => PSP22Error::Custom(String::from("AlreadyExists")),
+            AssetsError::NoDeposit => PSP22Error::Custom(String::from("NoDeposit")),
+            AssetsError::WouldBurn => PSP22Error::Custom(String::from("WouldBurn")),
+            AssetsError::LiveAsset => PSP22Error::Custom(String::from("LiveAsset")),
+            AssetsError::AssetNotLive => PSP22Error::Custom(String::from("AssetNotLive")),
+            AssetsError::IncorrectStatus => PSP22Error::Custom(String::from("IncorrectStatus")),
+            AssetsError::NotFrozen => PSP22Error::Custom(String::from("NotFrozen")),
+            AssetsError::CallbackFailed => PSP22Error::Custom(String::from("CallbackFailed")),
+            AssetsError::RuntimeError => PSP22Error::Custom(String::from("RuntimeError")),
+            AssetsError::UnknownStatusCode => PSP22Error::Custom(String::from("UnknownStatusCode")),
+            AssetsError::InvalidScaleEncoding => {
+                PSP22Error::Custom(String::from("InvalidScaleEncoding"))
+            }
+        }
+    }
+}
+
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Returns the total token supply.
+    #[ink(message)]
+    fn total_supply(&self) -> u128;
+
+    /// Returns the account balance for the specified `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    /// Returns the amount which `spender` is still allowed to withdraw from `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+
+    /// Transfers `value` amount of tokens from the caller's account to account `to`.
+    #[ink(message)]
+    fn transfer(&mut self, to: AccountId, value: u128, data: Vec<u8>) -> Result<(), PSP22Error>;
+
+    /// Transfers `value` tokens on the behalf of `from` to the account `to`.
+    #[ink(message)]
+    fn transfer_from(
+        &mut self,
+        from: AccountId,
+        to: AccountId,
+        value: u128,
+        data: Vec<u8>,
+    ) -> Result<(), PSP22Error>;
+
+    /// Allows `spender` to withdraw from the caller's account multiple times, up to
+    /// the total amount of `value`.
+    #[ink(message)]
+    fn approve(&mut self, spender: AccountId, value: u128) -> Result<(), PSP22Error>;
+
+    /// Increases by `delta_value` the allowance granted to `spender` by the caller.
+    #[ink(message)]
+    fn increase_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+
+    /// Decreases by `delta_value` the allowance granted to `spender` by the caller.
+    #[ink(message)]
+    fn decrease_allowance(
+        &mut self,
+        spender: AccountId,
+        delta_value: u128,
+    ) -> Result<(), PSP22Error>;
+}
+
+#[ink::trait_definition]
+pub trait PSP22Metadata {
+    /// Returns the token name.
+    #[ink(message)]
+    fn token_name(&self) -> Option<String>;
+
+    /// Returns the token symbol.
+    #[ink(message)]
+    fn token_symbol(&self) -> Option<String>;
+
+    /// Returns the token decimals.
+    #[ink(message)]
+    fn token_decimals(&self) -> u8;
+}
+
+#[ink::contract]
+mod psp22_assets {
+    use super::*;
+    use ink::storage::Mapping;
+
+    #[ink(storage)]
+    pub struct Psp22Assets {
+        /// ID the assets chain extension is registered under in the runtime
+        extension_id: u16,
+        /// Asset this token is bound to
+        asset_id: u128,
+        /// PSP22 allowances, `(owner, spender) -> amount`
+        allowances: Mapping<(AccountId, AccountId), Balance>,
+    }
+
+    #[ink(event)]
+    pub struct Transfer {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Approval {
+        #[ink(topic)]
+        owner: AccountId,
+        #[ink(topic)]
+        spender: AccountId,
+        amount: Balance,
+    }
+
+    impl Psp22Assets {
+        #[ink(constructor)]
+        pub fn new(extension_id: u16, asset_id: u128) -> Self {
+            Self {
+                extension_id,
+                asset_id,
+                allowances: Mapping::default(),
+            }
+        }
+
+        /// Returns the asset this token is bound to.
+        #[ink(message)]
+        pub fn asset_id(&self) -> u128 {
+            self.asset_id
+        }
+
+        fn transfer_impl(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+        ) -> Result<(), PSP22Error> {
+            if to == AccountId::from([0; 32]) {
+                return Err(PSP22Error::ZeroRecipientAddress);
+            }
+            self.assets()
+                .transfer_approved(self.asset_id, from, to, value)?;
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        fn approve_impl(&mut self, owner: AccountId, spender: AccountId, amount: Balance) {
+            self.allowances.insert((owner, spender), &amount);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                amount,
+            });
+        }
+
+        fn assets(&self) -> AssetsExtension {
+            AssetsExtension::new_with_extension_id(self.extension_id)
+        }
+    }
+
+    impl PSP22 for Psp22Assets {
+        #[ink(message)]
+        fn total_supply(&self) -> Balance {
+            self.assets().total_supply(self.asset_id)
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.assets().balance_of(self.asset_id, owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or_default()
+        }
+
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            self.transfer_impl(self.env().caller(), to, value)
+        }
+
+        #[ink(message)]
+        fn transfer_from(
+            &mut self,
+            from: AccountId,
+            to: AccountId,
+            value: Balance,
+            _data: Vec<u8>,
+        ) -> Result<(), PSP22Error> {
+            let spender = self.env().caller();
+            let allowance = self.allowance(from, spender);
+            if allowance < value {
+                return Err(PSP22Error::InsufficientAllowance);
+            }
+            self.transfer_impl(from, to, value)?;
+            self.approve_impl(from, spender, allowance - value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn approve(&mut self, spender: AccountId, value: Balance) -> Result<(), PSP22Error> {
+            self.approve_impl(self.env().caller(), spender, value);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            self.approve_impl(owner, spender, allowance.saturating_add(delta_value));
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            delta_value: Balance,
+        ) -> Result<(), PSP22Error> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            let amount = allowance
+                .checked_sub(delta_value)
+                .ok_or(PSP22Error::InsufficientAllowance)?;
+            self.approve_impl(owner, spender, amount);
+            Ok(())
+        }
+    }
+
+    impl PSP22Metadata for Psp22Assets {
+        #[ink(message)]
+        fn token_name(&self) -> Option<String> {
+            let name = self.assets().metadata_name(self.asset_id);
+            String::from_utf8(name).ok().filter(|name| !name.is_empty())
+        }
+
+        #[ink(message)]
+        fn token_symbol(&self) -> Option<String> {
+            let symbol = self.assets().metadata_symbol(self.asset_id);
+            String::from_utf8(symbol)
+                .ok()
+                .filter(|symbol| !symbol.is_empty())
+        }
+
+        #[ink(message)]
+        fn token_decimals(&self) -> u8 {
+            self.assets().metadata_decimals(self.asset_id)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use assets_chain_extension_types::Command;
+        use chain_extension_mock::MockExtension;
+        use ink::env::test::{default_accounts, recorded_events, set_caller};
+        use std::{cell::RefCell, collections::BTreeMap, rc::Rc};
+
+        const ASSET_ID: u128 = 1;
+
+        /// Assets chain extension, registered under its default ID
+        const ASSETS: MockExtension = MockExtension::new(2);
+
+        fn register_transfer_approved(status_code: u32) {
+            ASSETS.register(
+                Command::TransferApproved,
+                move |input: (u128, AccountId, AccountId, Balance)| {
+                    assert_eq!(input.0, ASSET_ID);
+                    (status_code, ())
+                },
+            );
+        }
+
+        #[ink::test]
+        fn transfer_works() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (alice, bob) = (accounts.alice, accounts.bob);
+            ASSETS.register(
+                Command::TransferApproved,
+                move |input: (u128, AccountId, AccountId, Balance)| {
+                    assert_eq!(input, (ASSET_ID, alice, bob, 100));
+                    (0, ())
+                },
+            );
+            set_caller::<DefaultEnvironment>(alice);
+
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+            assert_eq!(token.transfer(bob, 100, vec![]), Ok(()));
+            assert_eq!(recorded_events().count(), 1);
+        }
+
+        #[ink::test]
+        fn transfer_maps_assets_errors() {
+            let bob = default_accounts::<DefaultEnvironment>().bob;
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+
+            register_transfer_approved(AssetsError::BalanceLow as u32);
+            assert_eq!(
+                token.transfer(bob, 100, vec![]),
+                Err(PSP22Error::InsufficientBalance)
+            );
+            register_transfer_approved(AssetsError::Unapproved as u32);
+            assert_eq!(
+                token.transfer(bob, 100, vec![]),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            register_transfer_approved(AssetsError::WouldDie as u32);
+            assert_eq!(
+                token.transfer(bob, 100, vec![]),
+                Err(PSP22Error::Custom(String::from("WouldDie")))
+            );
+            assert_eq!(
+                token.transfer(AccountId::from([0; 32]), 100, vec![]),
+                Err(PSP22Error::ZeroRecipientAddress)
+            );
+        }
+
+        #[ink::test]
+        fn transfer_from_spends_allowance() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            register_transfer_approved(0);
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(token.approve(accounts.bob, 100), Ok(()));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.charlie, 101, vec![]),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.charlie, 60, vec![]),
+                Ok(())
+            );
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 40);
+        }
+
+        #[ink::test]
+        fn transfer_from_keeps_allowance_on_failure() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            register_transfer_approved(AssetsError::BalanceLow as u32);
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(token.approve(accounts.bob, 100), Ok(()));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                token.transfer_from(accounts.alice, accounts.charlie, 60, vec![]),
+                Err(PSP22Error::InsufficientBalance)
+            );
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 100);
+        }
+
+        #[ink::test]
+        fn allowance_adjustments_work() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+
+            assert_eq!(token.increase_allowance(accounts.bob, 50), Ok(()));
+            assert_eq!(token.increase_allowance(accounts.bob, 50), Ok(()));
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 100);
+            assert_eq!(token.decrease_allowance(accounts.bob, 30), Ok(()));
+            assert_eq!(token.allowance(accounts.alice, accounts.bob), 70);
+            assert_eq!(
+                token.decrease_allowance(accounts.bob, 71),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(recorded_events().count(), 3);
+        }
+
+        #[ink::test]
+        fn queries_work() {
+            let alice = default_accounts::<DefaultEnvironment>().alice;
+            ASSETS.register(Command::TotalSupply, |_: u128| (0, 1000 as Balance));
+            ASSETS.register(Command::BalanceOf, |_: (u128, AccountId)| {
+                (0, 100 as Balance)
+            });
+            ASSETS.register(Command::MetadataName, |_: u128| (0, b"Token".to_vec()));
+            ASSETS.register(Command::MetadataSymbol, |_: u128| (0, Vec::<u8>::new()));
+            ASSETS.register(Command::MetadataDecimals, |_: u128| (0, 18u8));
+
+            let token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+            assert_eq!(token.asset_id(), ASSET_ID);
+            assert_eq!(token.total_supply(), 1000);
+            assert_eq!(token.balance_of(alice), 100);
+            assert_eq!(token.token_name(), Some(String::from("Token")));
+            assert_eq!(token.token_symbol(), None);
+            assert_eq!(token.token_decimals(), 18);
+        }
+
+        #[ink::test]
+        fn transfer_follows_pallet_balances_and_approval() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let (bob, charlie) = (accounts.bob, accounts.charlie);
+            // `bob` holds 100 and approved the contract for 70 in the pallet
+            let balances = Rc::new(RefCell::new(BTreeMap::from([(bob, 100), (charlie, 0)])));
+            let approved = Rc::new(RefCell::new(70 as Balance));
+
+            let (state, approval) = (balances.clone(), approved.clone());
+            ASSETS.register(
+                Command::TransferApproved,
+                move |(_, owner, destination, amount): (u128, AccountId, AccountId, Balance)| {
+                    let mut balances = state.borrow_mut();
+                    if amount > *approval.borrow() {
+                        return (AssetsError::Unapproved as u32, ());
+                    }
+                    if amount > balances[&owner] {
+                        return (AssetsError::BalanceLow as u32, ());
+                    }
+                    *approval.borrow_mut() -= amount;
+                    *balances.get_mut(&owner).unwrap() -= amount;
+                    *balances.get_mut(&destination).unwrap() += amount;
+                    (0, ())
+                },
+            );
+            let state = balances.clone();
+            ASSETS.register(Command::BalanceOf, move |(_, who): (u128, AccountId)| {
+                (0, state.borrow()[&who])
+            });
+            set_caller::<DefaultEnvironment>(bob);
+            let mut token = Psp22Assets::new(ASSETS.id(), ASSET_ID);
+
+            // token can only move assets `bob` approved in the pallet
+            assert_eq!(
+                token.transfer(charlie, 80, vec![]),
+                Err(PSP22Error::InsufficientAllowance)
+            );
+            assert_eq!(token.transfer(charlie, 60, vec![]), Ok(()));
+            *approved.borrow_mut() = 1000;
+            assert_eq!(
+                token.transfer(charlie, 50, vec![]),
+                Err(PSP22Error::InsufficientBalance)
+            );
+            assert_eq!(token.balance_of(bob), 40);
+            assert_eq!(token.balance_of(charlie), 60);
+        }
+    }
+}
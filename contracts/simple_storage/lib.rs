@@ -27,5 +27,34 @@ mod simple_storage {
             let caller = self.env().caller();
             self.storages.get(caller).unwrap_or(0)
         }
+
+        #[ink(message, selector = 44)]
+        pub fn store_value(&mut self, value: u32) {
+            let caller = self.env().caller();
+            self.storages.insert(caller, &value);
+        }
+
+        /// Remove the caller's entry, refunding its storage deposit.
+        #[ink(message, selector = 45)]
+        pub fn remove(&mut self) {
+            let caller = self.env().caller();
+            self.storages.remove(caller);
+        }
+
+        #[ink(message, selector = 46)]
+        pub fn store_for(&mut self, key: AccountId, value: u32) {
+            self.storages.insert(key, &value);
+        }
+
+        #[ink(message, selector = 47)]
+        pub fn get_for(&self, key: AccountId) -> u32 {
+            self.storages.get(key).unwrap_or(0)
+        }
+
+        /// Remove the entry of `key`, refunding its storage deposit.
+        #[ink(message, selector = 48)]
+        pub fn remove_for(&mut self, key: AccountId) {
+            self.storages.remove(key);
+        }
     }
 }
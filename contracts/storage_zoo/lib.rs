@@ -0,0 +1,287 @@
+//! Contract with a variety of storage layouts, for checking that `pallet_contracts`
+//! storage migrations leave contract storage intact.
+//!
+//! All the values are derived from a seed, `checksum` hashes all of them. Record the
+//! checksum and getters output before the migration and compare them after it.
+//!
+//! Storage kinds:
+//! - packed struct & nested enum in the root storage
+//! - `Mapping` with `entries` values of the packed struct
+//! - `Lazy` values, including a nested enum
+//! - large `Lazy` vector of `vector_len` items, the runtime limits the max size
+//!   of a storage value
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod storage_zoo {
+    use ink::env::hash::Blake2x256;
+    use ink::prelude::vec::Vec;
+    use ink::storage::{Lazy, Mapping};
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Packed {
+        pub byte: u8,
+        pub short: u16,
+        pub word: u32,
+        pub long: u64,
+        pub wide: u128,
+        pub flag: bool,
+        pub account: AccountId,
+        pub bytes: Vec<u8>,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Shape {
+        Empty,
+        Scalar(u32),
+        Pair(u64, u128),
+        Tagged { tag: [u8; 4], inner: Inner },
+        List(Vec<Inner>),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Inner {
+        Nothing,
+        Leaf(u64),
+        Account(AccountId),
+        Packed(Packed),
+    }
+
+    #[ink(storage)]
+    pub struct StorageZoo {
+        seed: u64,
+        packed: Packed,
+        shape: Shape,
+        entries: Mapping<u32, Packed>,
+        entries_count: u32,
+        lazy_value: Lazy<u128>,
+        lazy_shape: Lazy<Shape>,
+        vector: Lazy<Vec<u64>>,
+    }
+
+    /// SplitMix64, deterministic source of all the stored values
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        fn wide(&mut self) -> u128 {
+            (self.next() as u128) << 64 | self.next() as u128
+        }
+
+        fn account(&mut self) -> AccountId {
+            let mut bytes = [0u8; 32];
+            for chunk in bytes.chunks_mut(8) {
+                chunk.copy_from_slice(&self.next().to_le_bytes());
+            }
+            AccountId::from(bytes)
+        }
+
+        fn packed(&mut self) -> Packed {
+            let len = (self.next() % 32) as usize;
+            Packed {
+                byte: self.next() as u8,
+                short: self.next() as u16,
+                word: self.next() as u32,
+                long: self.next(),
+                wide: self.wide(),
+                flag: self.next() & 1 == 0,
+                account: self.account(),
+                bytes: (0..len).map(|_| self.next() as u8).collect(),
+            }
+        }
+
+        fn inner(&mut self) -> Inner {
+            match self.next() % 4 {
+                0 => Inner::Nothing,
+                1 => Inner::Leaf(self.next()),
+                2 => Inner::Account(self.account()),
+                _ => Inner::Packed(self.packed()),
+            }
+        }
+
+        fn shape(&mut self) -> Shape {
+            match self.next() % 5 {
+                0 => Shape::Empty,
+                1 => Shape::Scalar(self.next() as u32),
+                2 => Shape::Pair(self.next(), self.wide()),
+                3 => Shape::Tagged {
+                    tag: (self.next() as u32).to_le_bytes(),
+                    inner: self.inner(),
+                },
+                _ => {
+                    let len = 1 + self.next() % 4;
+                    Shape::List((0..len).map(|_| self.inner()).collect())
+                }
+            }
+        }
+    }
+
+    impl StorageZoo {
+        #[ink(constructor)]
+        pub fn new(seed: u64, entries: u32, vector_len: u32) -> Self {
+            let mut rng = Rng(seed);
+            let mut contract = Self {
+                seed,
+                packed: rng.packed(),
+                shape: rng.shape(),
+                entries: Mapping::default(),
+                entries_count: 0,
+                lazy_value: Lazy::new(),
+                lazy_shape: Lazy::new(),
+                vector: Lazy::new(),
+            };
+            contract.fill(rng, entries, vector_len);
+            contract
+        }
+
+        /// Overwrite all the values with ones derived from `seed`, same as instantiating
+        /// with the same arguments. Entries of the previous seed are removed.
+        #[ink(message, selector = 42)]
+        pub fn seed(&mut self, seed: u64, entries: u32, vector_len: u32) {
+            for key in 0..self.entries_count {
+                self.entries.remove(key);
+            }
+
+            let mut rng = Rng(seed);
+            self.seed = seed;
+            self.packed = rng.packed();
+            self.shape = rng.shape();
+            self.fill(rng, entries, vector_len);
+        }
+
+        /// Blake2x256 hash of all the stored values.
+        #[ink(message, selector = 43)]
+        pub fn checksum(&self) -> [u8; 32] {
+            let entries: Vec<Option<Packed>> = (0..self.entries_count)
+                .map(|key| self.entries.get(key))
+                .collect();
+            let mut output = [0u8; 32];
+            ink::env::hash_encoded::<Blake2x256, _>(
+                &(
+                    self.seed,
+                    &self.packed,
+                    &self.shape,
+                    entries,
+                    self.lazy_value.get(),
+                    self.lazy_shape.get(),
+                    self.vector.get(),
+                ),
+                &mut output,
+            );
+            output
+        }
+
+        #[ink(message, selector = 44)]
+        pub fn seed_used(&self) -> u64 {
+            self.seed
+        }
+
+        #[ink(message, selector = 45)]
+        pub fn packed(&self) -> Packed {
+            self.packed.clone()
+        }
+
+        #[ink(message, selector = 46)]
+        pub fn shape(&self) -> Shape {
+            self.shape.clone()
+        }
+
+        #[ink(message, selector = 47)]
+        pub fn entry(&self, key: u32) -> Option<Packed> {
+            self.entries.get(key)
+        }
+
+        #[ink(message, selector = 48)]
+        pub fn entries_count(&self) -> u32 {
+            self.entries_count
+        }
+
+        #[ink(message, selector = 49)]
+        pub fn lazy_value(&self) -> Option<u128> {
+            self.lazy_value.get()
+        }
+
+        #[ink(message, selector = 50)]
+        pub fn lazy_shape(&self) -> Option<Shape> {
+            self.lazy_shape.get()
+        }
+
+        #[ink(message, selector = 51)]
+        pub fn vector_len(&self) -> u32 {
+            self.vector.get().unwrap_or_default().len() as u32
+        }
+
+        /// Up to `len` items of the vector, starting at `from`.
+        #[ink(message, selector = 52)]
+        pub fn vector_slice(&self, from: u32, len: u32) -> Vec<u64> {
+            let vector = self.vector.get().unwrap_or_default();
+            let from = (from as usize).min(vector.len());
+            let to = from.saturating_add(len as usize).min(vector.len());
+            vector[from..to].to_vec()
+        }
+
+        /// Fill the non-root storage, continuing with the `rng` used for the root.
+        fn fill(&mut self, mut rng: Rng, entries: u32, vector_len: u32) {
+            for key in 0..entries {
+                self.entries.insert(key, &rng.packed());
+            }
+            self.entries_count = entries;
+
+            self.lazy_value.set(&rng.wide());
+            self.lazy_shape.set(&rng.shape());
+
+            let vector: Vec<u64> = (0..vector_len).map(|_| rng.next()).collect();
+            self.vector.set(&vector);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        /// Checksum of a fresh instance, computed before the next one overwrites
+        /// the non-root storage of the off-chain contract.
+        fn checksum_of(seed: u64) -> [u8; 32] {
+            StorageZoo::new(seed, 5, 10).checksum()
+        }
+
+        #[ink::test]
+        fn same_seed_gives_same_checksum() {
+            assert_eq!(checksum_of(1), checksum_of(1));
+        }
+
+        #[ink::test]
+        fn different_seeds_give_different_checksums() {
+            assert_ne!(checksum_of(1), checksum_of(2));
+        }
+
+        #[ink::test]
+        fn reseeding_matches_instantiation() {
+            let expected = checksum_of(2);
+            let mut contract = StorageZoo::new(1, 8, 20);
+            contract.seed(2, 5, 10);
+            assert_eq!(contract.checksum(), expected);
+            assert_eq!(contract.entry(5), None);
+        }
+    }
+}
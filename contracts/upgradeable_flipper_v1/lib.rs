@@ -0,0 +1,92 @@
+// This file is part of Astar.
+
+// Copyright (C) 2019-2023 Stake Technologies Pte.Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// Astar is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Astar is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Astar. If not, see <http://www.gnu.org/licenses/>.
+
+//! Upgradeable flipper, v1.
+//! Can be upgraded to `upgradeable_flipper_v2` which has the same storage layout,
+//! only the reported `version` differs.
+//!
+//! v2 is a separate crate so it has a distinct code hash to upgrade to, `version`
+//! then proves the new code runs on top of the unchanged storage.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod flipper {
+    /// Storage layout shared by all the versions
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+        owner: AccountId,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Caller is not the owner
+        NotOwner,
+        /// `set_code_hash` failed, e.g. code hash not found
+        UpgradeFailed,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {
+            Self {
+                value: init_value,
+                owner: Self::env().caller(),
+            }
+        }
+
+        #[ink(message, selector = 42)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        #[ink(message, selector = 43)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+
+        /// Replace the code of contract with `code_hash`, takes effect from the
+        /// next call. Only callable by the owner.
+        #[ink(message, selector = 44)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)
+        }
+
+        #[ink(message, selector = 45)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message, selector = 46)]
+        pub fn version(&self) -> u8 {
+            1
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,118 @@
+// This file is part of Astar.
+
+// Copyright (C) 2019-2023 Stake Technologies Pte.Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// Astar is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Astar is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Astar. If not, see <http://www.gnu.org/licenses/>.
+
+//! Upgradeable flipper, v3.
+//! Adds a flip counter to the storage of `upgradeable_flipper_v1` & `v2`. Counter is
+//! stored under its own key, so the root storage stays compatible, and is missing
+//! until the owner calls `migrate` after upgrading from v1 or v2.
+
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+#[ink::contract]
+mod flipper {
+    use ink::storage::Lazy;
+
+    /// Storage layout shared by all the versions, plus `flips` added in v3
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+        owner: AccountId,
+        /// Number of flips since migration, `None` before migration
+        flips: Lazy<u32>,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        /// Caller is not the owner
+        NotOwner,
+        /// `set_code_hash` failed, e.g. code hash not found
+        UpgradeFailed,
+        /// `migrate` was already called or the contract was instantiated as v3
+        AlreadyMigrated,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {
+            let mut flips = Lazy::new();
+            flips.set(&0);
+            Self {
+                value: init_value,
+                owner: Self::env().caller(),
+                flips,
+            }
+        }
+
+        #[ink(message, selector = 42)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+            if let Some(flips) = self.flips.get() {
+                self.flips.set(&flips.saturating_add(1));
+            }
+        }
+
+        #[ink(message, selector = 43)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+
+        /// Replace the code of contract with `code_hash`, takes effect from the
+        /// next call. Only callable by the owner.
+        #[ink(message, selector = 44)]
+        pub fn upgrade(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.env()
+                .set_code_hash(&code_hash)
+                .map_err(|_| Error::UpgradeFailed)
+        }
+
+        #[ink(message, selector = 45)]
+        pub fn owner(&self) -> AccountId {
+            self.owner
+        }
+
+        #[ink(message, selector = 46)]
+        pub fn version(&self) -> u8 {
+            3
+        }
+
+        /// Initialize the storage added in v3. Only callable by the owner, once.
+        #[ink(message, selector = 47)]
+        pub fn migrate(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if self.flips.get().is_some() {
+                return Err(Error::AlreadyMigrated);
+            }
+            self.flips.set(&0);
+            Ok(())
+        }
+
+        #[ink(message, selector = 48)]
+        pub fn flips(&self) -> Option<u32> {
+            self.flips.get()
+        }
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                return Err(Error::NotOwner);
+            }
+            Ok(())
+        }
+    }
+}
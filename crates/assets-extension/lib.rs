@@ -16,6 +16,11 @@
 // You should have received a copy of the GNU General Public License
 // along with Astar. If not, see <http://www.gnu.org/licenses/>.
 
+//! Interface of the `pallet-assets` chain extension, shared by the contracts
+//! using it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
 use core::marker::PhantomData;
 use ink::env::{DefaultEnvironment, Environment};
 use scale::{Encode, Decode};
@@ -23,15 +28,35 @@ use ink::prelude::vec::Vec;
 use assets_chain_extension_types::Command;
 
 /// Pallet Assets Extension Interface
-pub struct AssetsExtension<E = DefaultEnvironment, const ID: u16 = 02>(PhantomData<E>);
+pub struct AssetsExtension<E = DefaultEnvironment> {
+    /// ID the extension is registered under in the runtime
+    id: u16,
+    _env: PhantomData<E>,
+}
+
+impl<E: Environment> AssetsExtension<E> {
+    /// ID the extension is registered under in Astar runtimes
+    pub const DEFAULT_ID: u16 = 2;
+
+    /// Extension registered under `DEFAULT_ID`
+    pub const fn new() -> Self {
+        Self::new_with_extension_id(Self::DEFAULT_ID)
+    }
 
-impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
-    const fn get_func_id(idx: u16) -> u32 {
-        ((ID as u32) << 16) + (idx as u32)
+    /// Extension registered under the given `id`
+    pub const fn new_with_extension_id(id: u16) -> Self {
+        Self {
+            id,
+            _env: PhantomData,
+        }
     }
 
-    pub fn transfer(id: u128, target: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
-        let func_id: u32 = Self::get_func_id(Command::Transfer.into());
+    const fn get_func_id(&self, idx: u16) -> u32 {
+        ((self.id as u32) << 16) + (idx as u32)
+    }
+
+    pub fn transfer(&self, id: u128, target: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
+        let func_id: u32 = self.get_func_id(Command::Transfer.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::Balance)>()
@@ -40,8 +65,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, target, amount))
     }
 
-    pub fn transfer_approved(id: u128, owner: E::AccountId, destination: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
-        let func_id: u32 = Self::get_func_id(Command::TransferApproved.into());
+    pub fn transfer_approved(&self, id: u128, owner: E::AccountId, destination: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
+        let func_id: u32 = self.get_func_id(Command::TransferApproved.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::AccountId, E::Balance)>()
@@ -50,8 +75,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, owner, destination, amount))
     }
 
-    pub fn mint(id: u128, beneficiary: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
-        let func_id: u32 = Self::get_func_id(Command::Mint.into());
+    pub fn mint(&self, id: u128, beneficiary: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
+        let func_id: u32 = self.get_func_id(Command::Mint.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::Balance)>()
@@ -60,8 +85,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, beneficiary, amount))
     }
 
-    pub fn burn(id: u128, who: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
-        let func_id: u32 = Self::get_func_id(Command::Burn.into());
+    pub fn burn(&self, id: u128, who: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
+        let func_id: u32 = self.get_func_id(Command::Burn.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::Balance)>()
@@ -70,8 +95,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, who, amount))
     }
 
-    pub fn approve_transfer(id: u128, delegate: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
-        let func_id: u32 = Self::get_func_id(Command::ApproveTransfer.into());
+    pub fn approve_transfer(&self, id: u128, delegate: E::AccountId, amount: E::Balance) -> Result<(), AssetsError> {
+        let func_id: u32 = self.get_func_id(Command::ApproveTransfer.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::Balance)>()
@@ -80,8 +105,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, delegate, amount))
     }
 
-    pub fn balance_of(id: u128, who: E::AccountId) -> E::Balance {
-        let func_id: u32 = Self::get_func_id(Command::BalanceOf.into());
+    pub fn balance_of(&self, id: u128, who: E::AccountId) -> E::Balance {
+        let func_id: u32 = self.get_func_id(Command::BalanceOf.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId)>()
@@ -90,8 +115,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, who))
     }
 
-    pub fn allowance(id: u128, owner: E::AccountId, delegate: E::AccountId) -> E::Balance {
-        let func_id: u32 = Self::get_func_id(Command::Allowance.into());
+    pub fn allowance(&self, id: u128, owner: E::AccountId, delegate: E::AccountId) -> E::Balance {
+        let func_id: u32 = self.get_func_id(Command::Allowance.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<(u128, E::AccountId, E::AccountId)>()
@@ -100,8 +125,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&(id, owner, delegate))
     }
 
-    pub fn total_supply(id: u128) -> E::Balance {
-        let func_id: u32 = Self::get_func_id(Command::TotalSupply.into());
+    pub fn total_supply(&self, id: u128) -> E::Balance {
+        let func_id: u32 = self.get_func_id(Command::TotalSupply.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<u128>()
@@ -110,8 +135,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&id)
     }
 
-    pub fn metadata_name(id: u128) -> Vec<u8> {
-        let func_id: u32 = Self::get_func_id(Command::MetadataName.into());
+    pub fn metadata_name(&self, id: u128) -> Vec<u8> {
+        let func_id: u32 = self.get_func_id(Command::MetadataName.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<u128>()
@@ -120,8 +145,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&id)
     }
 
-    pub fn metadata_symbol(id: u128) -> Vec<u8> {
-        let func_id: u32 = Self::get_func_id(Command:: MetadataSymbol.into());
+    pub fn metadata_symbol(&self, id: u128) -> Vec<u8> {
+        let func_id: u32 = self.get_func_id(Command:: MetadataSymbol.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<u128>()
@@ -130,8 +155,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&id)
     }
 
-    pub fn metadata_decimals(id: u128) -> u8 {
-        let func_id: u32 = Self::get_func_id(Command::MetadataDecimals.into());
+    pub fn metadata_decimals(&self, id: u128) -> u8 {
+        let func_id: u32 = self.get_func_id(Command::MetadataDecimals.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<u128>()
@@ -140,8 +165,8 @@ impl<E: Environment, const ID: u16> AssetsExtension<E, ID> {
             .call(&id)
     }
 
-    pub fn minimum_balance(id: u128) -> E::Balance {
-        let func_id: u32 = Self::get_func_id(Command::MinimumBalance.into());
+    pub fn minimum_balance(&self, id: u128) -> E::Balance {
+        let func_id: u32 = self.get_func_id(Command::MinimumBalance.into());
 
         ::ink::env::chain_extension::ChainExtensionMethod::build(func_id)
             .input::<u128>()
@@ -238,4 +263,4 @@ impl From<scale::Error> for AssetsError {
     fn from(_: scale::Error) -> Self {
         AssetsError::InvalidScaleEncoding
     }
-}
\ No newline at end of file
+}
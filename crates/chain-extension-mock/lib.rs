@@ -0,0 +1,90 @@
+// This file is part of Astar.
+
+// Copyright (C) 2019-2023 Stake Technologies Pte.Ltd.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// Astar is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Astar is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Astar. If not, see <http://www.gnu.org/licenses/>.
+
+//! Off-chain mocks of chain extensions, registered through ink!'s
+//! `register_chain_extension`. Only meant for the contracts' unit tests.
+
+use ink::env::test::{register_chain_extension, ChainExtension};
+use scale::{Decode, Encode};
+
+/// Mock of a single chain extension function, input is decoded and
+/// passed to the handler which returns the status code and output.
+pub struct MockCommand<F> {
+    func_id: u32,
+    handler: F,
+}
+
+impl<F: FnMut(&[u8]) -> (u32, Vec<u8>)> ChainExtension for MockCommand<F> {
+    fn func_id(&self) -> u32 {
+        self.func_id
+    }
+
+    fn call(&mut self, input: &[u8], output: &mut Vec<u8>) -> u32 {
+        // off-chain engine encodes the already encoded input once more
+        let input = Vec::<u8>::decode(&mut &input[..]).expect("input is encoded bytes");
+        let (status_code, out) = (self.handler)(&input);
+        output.extend(out);
+        status_code
+    }
+}
+
+/// Chain extension registered under `id`, commands are mocked one by one.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MockExtension {
+    id: u16,
+}
+
+impl MockExtension {
+    pub const fn new(id: u16) -> Self {
+        Self { id }
+    }
+
+    /// ID the extension is registered under
+    pub const fn id(&self) -> u16 {
+        self.id
+    }
+
+    /// Register mock for given command, replacing any previously registered one.
+    pub fn register<I, O, F>(&self, command: impl Into<u16>, mut handler: F)
+    where
+        I: Decode,
+        O: Encode,
+        F: FnMut(I) -> (u32, O) + 'static,
+    {
+        register_chain_extension(MockCommand {
+            func_id: self.func_id(command),
+            handler: move |mut input: &[u8]| {
+                let input = I::decode(&mut input).expect("invalid input for command");
+                let (status_code, out) = handler(input);
+                (status_code, out.encode())
+            },
+        });
+    }
+
+    /// Register mock for given command which always returns given status code.
+    pub fn register_status_code(&self, command: impl Into<u16>, status_code: u32) {
+        register_chain_extension(MockCommand {
+            func_id: self.func_id(command),
+            handler: move |_: &[u8]| (status_code, Vec::new()),
+        });
+    }
+
+    fn func_id(&self, command: impl Into<u16>) -> u32 {
+        ((self.id as u32) << 16) + command.into() as u32
+    }
+}